@@ -1,12 +1,368 @@
+use std::env;
+use std::fmt;
 use std::fs;
+use std::hint;
+use std::io;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
 
-pub fn load_input() -> String {
-    let inputfile: String = match std::env::args().nth(1) {
-        None => "input.txt".to_string(),
-        Some(x) => x,
+#[derive(Debug)]
+pub enum Error {
+    // The puzzle input couldn't be read.
+    Io(String, io::Error),
+    // The puzzle input wasn't in the expected format.  Lines and columns count from 1.
+    Parse {
+        line: usize,
+        column: usize,
+        msg: String,
+    },
+}
+
+impl Error {
+    // An error at the given column of a single line of input.  Parsers that only see one line
+    // report it as line 1; parse_lines() or below() moves it to where the line really is.
+    pub fn parse(column: usize, msg: impl Into<String>) -> Error {
+        Error::at(1, column, msg)
+    }
+
+    pub fn at(line: usize, column: usize, msg: impl Into<String>) -> Error {
+        Error::Parse {
+            line,
+            column,
+            msg: msg.into(),
+        }
+    }
+
+    // Shift the error down by `lines`, for parsers that were handed a piece of the input that
+    // starts part way through it.
+    pub fn below(self, lines: usize) -> Error {
+        match self {
+            Error::Parse { line, column, msg } => Error::Parse {
+                line: line + lines,
+                column,
+                msg,
+            },
+            other => other,
+        }
+    }
+
+    // Describe the error, quoting the offending line of `text` with a caret under the column.
+    pub fn report(&self, text: &str) -> String {
+        let (line, column) = match self {
+            Error::Parse { line, column, .. } => (*line, *column),
+            Error::Io(..) => return self.to_string(),
+        };
+        let source = match line.checked_sub(1).and_then(|i| text.lines().nth(i)) {
+            Some(source) => source,
+            None => return self.to_string(),
+        };
+        let caret = " ".repeat(column.saturating_sub(1)) + "^";
+        format!("{}\n    {}\n    {}", self, source, caret)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(path, e) => write!(f, "{}: {}", path, e),
+            Error::Parse { line, column, msg } => {
+                write!(f, "line {}, column {}: {}", line, column, msg)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(_, e) => Some(e),
+            Error::Parse { .. } => None,
+        }
+    }
+}
+
+// Parse `text`, which starts at `column` of its line, as a number.
+pub fn parse_number<T: FromStr>(text: &str, column: usize) -> Result<T, Error> {
+    text.trim()
+        .parse::<T>()
+        .map_err(|_| Error::parse(column, format!("expected a number, found {:?}", text)))
+}
+
+// Parse every line of `text` with `f`, pointing any error at the line it came from.
+pub fn parse_lines<'a, T, F>(text: &'a str, mut f: F) -> Result<Vec<T>, Error>
+where
+    F: FnMut(&'a str) -> Result<T, Error>,
+{
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| f(line).map_err(|e| e.below(i)))
+        .collect()
+}
+
+// Parse every blank-line separated section of `text` with `f`, pointing any error at the line it
+// came from.  Sections that are only whitespace are skipped.
+pub fn parse_sections<'a, T, F>(text: &'a str, mut f: F) -> Result<Vec<T>, Error>
+where
+    F: FnMut(&'a str) -> Result<T, Error>,
+{
+    let mut result = Vec::new();
+    let mut line = 0;
+    for section in text.split("\n\n") {
+        if !section.trim().is_empty() {
+            result.push(f(section).map_err(|e| e.below(line))?);
+        }
+        line += section.lines().count() + 1;
+    }
+    return Ok(result);
+}
+
+// The column at which `part`, which must be a slice of `line`, starts.
+pub fn column_of(line: &str, part: &str) -> usize {
+    return part.as_ptr() as usize - line.as_ptr() as usize + 1;
+}
+
+// A day's puzzle, in stages: parse the input text once, then solve each part from the parsed
+// form.  Input may borrow from the text, which is why it carries a lifetime.
+pub trait Solution {
+    // Day of the month, 1 through 25.
+    const DAY: u32;
+
+    type Input<'a>;
+    type Answer1: fmt::Display;
+    type Answer2: fmt::Display;
+
+    fn parse(text: &str) -> Result<Self::Input<'_>, Error>;
+    fn part1(input: &Self::Input<'_>) -> Self::Answer1;
+    fn part2(input: &Self::Input<'_>) -> Self::Answer2;
+}
+
+// The type-erased view of a Solution that the `advent` runner keeps in its registry.  Every
+// Solution is a Day; there's no need to implement this by hand.
+pub trait Day {
+    fn day(&self) -> u32;
+    fn part1(&self, text: &str) -> Result<String, Error>;
+    fn part2(&self, text: &str) -> Result<String, Error>;
+    // Run every stage once, timing each of them separately.
+    fn time(&self, text: &str) -> Result<Timings, Error>;
+}
+
+// How long each stage of one run of a Solution took.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+impl<S: Solution> Day for S {
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn part1(&self, text: &str) -> Result<String, Error> {
+        let input = S::parse(text)?;
+        Ok(<S as Solution>::part1(&input).to_string())
+    }
+
+    fn part2(&self, text: &str) -> Result<String, Error> {
+        let input = S::parse(text)?;
+        Ok(<S as Solution>::part2(&input).to_string())
+    }
+
+    fn time(&self, text: &str) -> Result<Timings, Error> {
+        let start = Instant::now();
+        let input = S::parse(text)?;
+        let parse = start.elapsed();
+
+        // Format the answers inside the timed region, in case an answer does its work lazily.
+        let start = Instant::now();
+        hint::black_box(<S as Solution>::part1(&input).to_string());
+        let part1 = start.elapsed();
+
+        let start = Instant::now();
+        hint::black_box(<S as Solution>::part2(&input).to_string());
+        let part2 = start.elapsed();
+        Ok(Timings {
+            parse,
+            part1,
+            part2,
+        })
+    }
+}
+
+// Where a day's puzzle input comes from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+    // Text given directly, such as an example from the puzzle description.
+    Inline(String),
+}
+
+impl Source {
+    // A command line argument naming the input: "-" is stdin, anything else is a path.
+    pub fn from_arg(arg: &str) -> Source {
+        match arg {
+            "-" => Source::Stdin,
+            path => Source::File(PathBuf::from(path)),
+        }
+    }
+
+    // Where a day's input lives when nothing else is given: $ADVENT_INPUTS/dayN.txt if that
+    // variable is set, otherwise `fallback`, which is usually the day's own input.txt.
+    pub fn default_for(day: u32, fallback: impl Into<PathBuf>) -> Source {
+        match env::var_os(INPUTS_VAR) {
+            Some(dir) => Source::File(Path::new(&dir).join(format!("day{}.txt", day))),
+            None => Source::File(fallback.into()),
+        }
+    }
+
+    // How to refer to the source in diagnostics.
+    pub fn name(&self) -> String {
+        match self {
+            Source::Stdin => "<stdin>".to_string(),
+            Source::File(path) => path.display().to_string(),
+            Source::Inline(_) => "<inline>".to_string(),
+        }
+    }
+
+    pub fn read(&self) -> Result<String, Error> {
+        let result = match self {
+            Source::Stdin => {
+                let mut text = String::new();
+                io::stdin().read_to_string(&mut text).map(|_| text)
+            }
+            Source::File(path) => fs::read_to_string(path),
+            Source::Inline(text) => Ok(text.clone()),
+        };
+        return result.map_err(|e| Error::Io(self.name(), e));
+    }
+}
+
+// The environment variable naming a directory of inputs, one dayN.txt per day.
+pub const INPUTS_VAR: &str = "ADVENT_INPUTS";
+
+// Read a day's input: from the file named on the command line ("-" for stdin), or else from
+// the day's default Source.  The size goes to stderr so stdout carries only answers.
+pub fn load_input(day: u32) -> Result<String, Error> {
+    let source = match env::args().nth(1) {
+        Some(arg) => Source::from_arg(&arg),
+        None => Source::default_for(day, "input.txt"),
     };
+    let contents = source.read()?;
+    eprintln!("{}: {} bytes", source.name(), contents.len());
+    return Ok(contents);
+}
+
+// Print an error and give up.
+fn fail(day: u32, msg: String) -> ! {
+    eprintln!("day {}: {}", day, msg);
+    process::exit(1);
+}
+
+// Solve both parts of a day's puzzle for the input named on the command line.  This is all most
+// days' main() needs to do.
+pub fn solve<S: Solution>() {
+    let text = match load_input(S::DAY) {
+        Ok(text) => text,
+        Err(e) => fail(S::DAY, e.to_string()),
+    };
+    let input = match S::parse(&text) {
+        Ok(input) => input,
+        Err(e) => fail(S::DAY, e.report(&text)),
+    };
+    println!("part 1: {}", S::part1(&input));
+    println!("part 2: {}", S::part2(&input));
+}
+
+// Turn a day's worked examples into #[test]s, one per example, each checking the answers given
+// for it.  Parts without an expected answer aren't run.  Usually this lives in tests/examples.rs:
+//
+//   advent::examples! {
+//       day10::Puzzle;
+//       small: "16\n10\n15\n5\n1\n11\n7\n19\n6\n12\n4" => part1 = 35, part2 = 8;
+//       large: include_str!("../example.txt") => part1 = 220, part2 = 19208;
+//   }
+//
+// Answers are compared as text, so they can be numbers or strings.  Attributes such as #[ignore]
+// can be put in front of an example.
+#[macro_export]
+macro_rules! examples {
+    ($solution:ty; $($(#[$meta:meta])* $name:ident: $text:expr => $($part:ident = $answer:expr),+;)*) => {
+        $(
+            #[test]
+            $(#[$meta])*
+            fn $name() {
+                let text: &str = $text;
+                let input = match <$solution as $crate::Solution>::parse(text) {
+                    Ok(input) => input,
+                    Err(e) => panic!("{}", e.report(text)),
+                };
+                $(
+                    assert_eq!(
+                        <$solution as $crate::Solution>::$part(&input).to_string(),
+                        $answer.to_string(),
+                        "{} of example {}",
+                        stringify!($part),
+                        stringify!($name),
+                    );
+                )+
+            }
+        )*
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lines() {
+        let nums: Vec<i32> = parse_lines("1\n2\n3", |x| parse_number(x, 1)).unwrap();
+        assert_eq!(nums, [1, 2, 3]);
+
+        let e = parse_lines("1\n2\nx", |x| parse_number::<i32>(x, 1)).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 3, column 1: expected a number, found \"x\""
+        );
+    }
+
+    #[test]
+    fn test_parse_sections() {
+        let text = "a\nb\n\nc\n\nd\ne\nf";
+        let e = parse_sections(text, |s| match s.find('f') {
+            None => Ok(s),
+            Some(_) => Err(Error::at(3, 1, "found f")),
+        })
+        .unwrap_err();
+        assert_eq!(e.to_string(), "line 8, column 1: found f");
+    }
+
+    #[test]
+    fn test_report() {
+        let text = "nop +0\nacc +1\nfoo +3\n";
+        let e = Error::at(3, 5, "bad operand");
+        assert_eq!(
+            e.report(text),
+            "line 3, column 5: bad operand\n    foo +3\n        ^"
+        );
+        assert_eq!(column_of(text, &text[7..]), 8);
+    }
+
+    #[test]
+    fn test_source() {
+        assert_eq!(Source::from_arg("-"), Source::Stdin);
+        assert_eq!(
+            Source::from_arg("x.txt"),
+            Source::File(PathBuf::from("x.txt"))
+        );
+        assert_eq!(Source::Inline("1\n2".to_string()).read().unwrap(), "1\n2");
 
-    let contents = fs::read_to_string(&inputfile).expect("Something went wrong reading the file");
-    println!("{}: {} bytes", inputfile, contents.len());
-    return contents;
+        let e = Source::from_arg("no/such/file").read().unwrap_err();
+        assert!(e.to_string().starts_with("no/such/file: "));
+    }
 }
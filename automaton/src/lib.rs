@@ -0,0 +1,441 @@
+// Sparse cellular automata, over any neighbourhood and under any birth/survival rules.
+//
+// An Automaton keeps only its live cells, in a HashSet hashed by S.  Each step, every live cell
+// adds one to the count of each of its neighbours, so one pass over the live cells finds every
+// cell that could change along with how many live neighbours it has.  Rules then say which dead
+// cells come alive (birth) and which live cells stay alive (survival).
+//
+// A Neighbourhood says what a cell is and which cells are its neighbours:
+//
+//   Hex           the six tiles around a hexagon, as on day 24's floor
+//   Moore<D>      the 3^D - 1 cells around a cell: day 11's seats in 2D, day 17's cubes in 3D and 4D
+//   VonNeumann<D> the cells in D dimensions that share a face with a cell
+
+use advent::Error;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+
+// Which neighbour counts make a cell live.  Written the usual way for cellular automata, such
+// as "B3/S23": born with 3 live neighbours, survives with 2 or 3.  Counts past 9 need commas,
+// and can be ranges: "B3,10-12/S2,3".
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rules {
+    birth: Vec<usize>,
+    survival: Vec<usize>,
+}
+
+impl Rules {
+    // Conway's Game of Life, which is also day 17's rules.
+    pub fn conway() -> Rules {
+        Rules {
+            birth: vec![3],
+            survival: vec![2, 3],
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Rules, Error> {
+        let parts: Vec<&str> = text.split('/').collect();
+        let (b, s) = match parts.as_slice() {
+            [b, s] if b.starts_with('B') && s.starts_with('S') => (*b, *s),
+            _ => return Err(Error::parse(1, "expected rules like \"B3/S23\"")),
+        };
+        let birth = Rules::counts(text, &b[1..])?;
+        let survival = Rules::counts(text, &s[1..])?;
+        Ok(Rules { birth, survival })
+    }
+
+    // The counts in one half of the rules, which is a slice of `text`.
+    fn counts(text: &str, list: &str) -> Result<Vec<usize>, Error> {
+        let mut counts = Vec::new();
+        if !list.contains(',') && !list.contains('-') {
+            for (i, ch) in list.char_indices() {
+                match ch.to_digit(10) {
+                    Some(n) => counts.push(n as usize),
+                    None => {
+                        let column = advent::column_of(text, list) + i;
+                        return Err(Error::parse(
+                            column,
+                            format!("expected a digit, found {:?}", ch),
+                        ));
+                    }
+                }
+            }
+            return Ok(counts);
+        }
+        for item in list.split(',') {
+            let column = advent::column_of(text, item);
+            match item.split_once('-') {
+                Some((low, high)) => {
+                    let low: usize = advent::parse_number(low, column)?;
+                    let high: usize = advent::parse_number(high, advent::column_of(text, high))?;
+                    counts.extend(low..=high);
+                }
+                None => counts.push(advent::parse_number(item, column)?),
+            }
+        }
+        return Ok(counts);
+    }
+
+    pub fn born(&self, count: usize) -> bool {
+        self.birth.contains(&count)
+    }
+
+    pub fn survives(&self, count: usize) -> bool {
+        self.survival.contains(&count)
+    }
+}
+
+impl fmt::Display for Rules {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let list = |counts: &[usize]| {
+            let text: Vec<String> = counts.iter().map(|c| c.to_string()).collect();
+            match counts.iter().all(|c| *c < 10) {
+                true => text.concat(),
+                false => text.join(","),
+            }
+        };
+        write!(f, "B{}/S{}", list(&self.birth), list(&self.survival))
+    }
+}
+
+pub trait Neighbourhood {
+    type Cell: Copy + Eq + Hash;
+    // Call f with each neighbour of cell.
+    fn neighbours(&self, cell: Self::Cell, f: impl FnMut(Self::Cell));
+}
+
+// Hexagons, in the coordinates of the hexgrid crate.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Hex;
+
+impl Neighbourhood for Hex {
+    type Cell = hexgrid::Hex;
+
+    fn neighbours(&self, cell: hexgrid::Hex, f: impl FnMut(hexgrid::Hex)) {
+        cell.neighbours().iter().copied().for_each(f);
+    }
+}
+
+// Every cell that differs by at most one along every axis.
+#[derive(Clone, Debug)]
+pub struct Moore<const D: usize> {
+    deltas: Vec<[isize; D]>,
+}
+
+impl<const D: usize> Moore<D> {
+    pub fn new() -> Moore<D> {
+        let mut deltas = vec![[0isize; D]];
+        for axis in 0..D {
+            deltas = deltas
+                .into_iter()
+                .flat_map(|delta| {
+                    (-1..=1).map(move |d| {
+                        let mut delta = delta;
+                        delta[axis] = d;
+                        delta
+                    })
+                })
+                .collect();
+        }
+        deltas.retain(|delta| delta.iter().any(|d| *d != 0));
+        Moore { deltas }
+    }
+}
+
+impl<const D: usize> Default for Moore<D> {
+    fn default() -> Moore<D> {
+        Moore::new()
+    }
+}
+
+impl<const D: usize> Neighbourhood for Moore<D> {
+    type Cell = [isize; D];
+
+    fn neighbours(&self, cell: [isize; D], mut f: impl FnMut([isize; D])) {
+        for delta in &self.deltas {
+            let mut n = cell;
+            for (n, d) in n.iter_mut().zip(delta) {
+                *n += d;
+            }
+            f(n);
+        }
+    }
+}
+
+// Every cell one step away along a single axis.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct VonNeumann<const D: usize>;
+
+impl<const D: usize> Neighbourhood for VonNeumann<D> {
+    type Cell = [isize; D];
+
+    fn neighbours(&self, cell: [isize; D], mut f: impl FnMut([isize; D])) {
+        for axis in 0..D {
+            for d in [-1, 1] {
+                let mut n = cell;
+                n[axis] += d;
+                f(n);
+            }
+        }
+    }
+}
+
+// The live cells of an automaton over N, hashed by S.
+//
+// Most automata are unbounded, so any cell can come alive.  A bounded one only has the cells it
+// was given, such as the seats on day 11, and can be born with no live neighbours at all: B0.
+#[derive(Clone)]
+pub struct Automaton<N: Neighbourhood, S = hashers::Std> {
+    neighbourhood: N,
+    rules: Rules,
+    live: HashSet<N::Cell, S>,
+    bounds: Option<HashSet<N::Cell, S>>,
+}
+
+impl<N: Neighbourhood, S: BuildHasher + Default> Automaton<N, S> {
+    // An unbounded automaton, with no live cells yet.  The rules can't have B0, which would
+    // bring the whole infinite grid to life at once.
+    pub fn new(neighbourhood: N, rules: Rules) -> Automaton<N, S> {
+        assert!(!rules.born(0), "an unbounded automaton can't have B0");
+        Automaton {
+            neighbourhood,
+            rules,
+            live: HashSet::default(),
+            bounds: None,
+        }
+    }
+
+    // An automaton with only the given cells, all of them dead for now.
+    pub fn bounded(
+        neighbourhood: N,
+        rules: Rules,
+        cells: impl IntoIterator<Item = N::Cell>,
+    ) -> Automaton<N, S> {
+        Automaton {
+            neighbourhood,
+            rules,
+            live: HashSet::default(),
+            bounds: Some(cells.into_iter().collect()),
+        }
+    }
+
+    // Bring a cell to life, which has to be one of a bounded automaton's cells.
+    pub fn insert(&mut self, cell: N::Cell) {
+        if let Some(bounds) = &self.bounds {
+            assert!(bounds.contains(&cell), "cell is out of bounds");
+        }
+        self.live.insert(cell);
+    }
+
+    pub fn contains(&self, cell: &N::Cell) -> bool {
+        return self.live.contains(cell);
+    }
+
+    // The number of live cells.
+    pub fn len(&self) -> usize {
+        return self.live.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.live.is_empty();
+    }
+
+    pub fn live(&self) -> impl Iterator<Item = &N::Cell> {
+        return self.live.iter();
+    }
+
+    // Run one step, and return whether any cell changed.
+    pub fn step(&mut self) -> bool {
+        let mut counts: HashMap<N::Cell, usize, S> = HashMap::default();
+        for &cell in &self.live {
+            self.neighbourhood
+                .neighbours(cell, |n| *counts.entry(n).or_insert(0) += 1);
+        }
+        let rules = &self.rules;
+        let live = &self.live;
+        let alive = |cell: &N::Cell, count: usize| match live.contains(cell) {
+            true => rules.survives(count),
+            false => rules.born(count),
+        };
+        let next: HashSet<N::Cell, S> = match &self.bounds {
+            Some(bounds) => bounds
+                .iter()
+                .filter(|cell| alive(cell, counts.get(cell).copied().unwrap_or(0)))
+                .copied()
+                .collect(),
+            None => {
+                let mut next: HashSet<N::Cell, S> = counts
+                    .iter()
+                    .filter(|(cell, count)| alive(cell, **count))
+                    .map(|(cell, _)| *cell)
+                    .collect();
+                // Live cells with no live neighbours never got a count.
+                if rules.survives(0) {
+                    next.extend(live.iter().filter(|cell| !counts.contains_key(cell)));
+                }
+                next
+            }
+        };
+        let changed = next != self.live;
+        self.live = next;
+        return changed;
+    }
+
+    // Step until nothing changes, and return how many steps changed something.
+    pub fn settle(&mut self) -> usize {
+        let mut steps = 0;
+        while self.step() {
+            steps += 1;
+        }
+        return steps;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The cells of a picture of '#' (live) and anything else (dead), with x = column and
+    // y = row, and 0 on any other axes.
+    fn picture<const D: usize>(text: &str, ch: char) -> Vec<[isize; D]> {
+        let mut cells = Vec::new();
+        for (y, line) in text.lines().enumerate() {
+            for (x, _) in line.chars().enumerate().filter(|(_, c)| *c == ch) {
+                let mut cell = [0; D];
+                cell[0] = x as isize;
+                cell[1] = y as isize;
+                cells.push(cell);
+            }
+        }
+        return cells;
+    }
+
+    fn life<N: Neighbourhood>(neighbourhood: N, rules: &str, live: &[N::Cell]) -> Automaton<N> {
+        let mut automaton = Automaton::new(neighbourhood, Rules::parse(rules).unwrap());
+        for &cell in live {
+            automaton.insert(cell);
+        }
+        return automaton;
+    }
+
+    #[test]
+    fn test_rules() {
+        assert_eq!(Rules::parse("B3/S23").unwrap(), Rules::conway());
+        assert_eq!(Rules::conway().to_string(), "B3/S23");
+        let rules = Rules::parse("B3,10-12/S").unwrap();
+        assert!(rules.born(11));
+        assert!(!rules.survives(2));
+        assert_eq!(rules.to_string(), "B3,10,11,12/S");
+        assert!(Rules::parse("B0/S0123").unwrap().born(0));
+
+        let error = |text: &str| Rules::parse(text).unwrap_err().to_string();
+        assert_eq!(
+            error("S23/B3"),
+            "line 1, column 1: expected rules like \"B3/S23\""
+        );
+        assert_eq!(
+            error("B3/S2x"),
+            "line 1, column 6: expected a digit, found 'x'"
+        );
+        assert_eq!(
+            error("B3/S2,x"),
+            "line 1, column 7: expected a number, found \"x\""
+        );
+    }
+
+    fn count<N: Neighbourhood>(neighbourhood: N, cell: N::Cell) -> usize {
+        let mut count = 0;
+        neighbourhood.neighbours(cell, |_| count += 1);
+        return count;
+    }
+
+    #[test]
+    fn test_neighbourhoods() {
+        assert_eq!(count(Hex, hexgrid::Hex::ORIGIN), 6);
+        assert_eq!(count(Moore::<2>::new(), [0; 2]), 8);
+        assert_eq!(count(Moore::<4>::new(), [0; 4]), 80);
+        assert_eq!(count(VonNeumann::<3>, [0; 3]), 6);
+    }
+
+    #[test]
+    fn test_moore() {
+        let mut blinker = life(Moore::<2>::new(), "B3/S23", &[[0, 1], [1, 1], [2, 1]]);
+        assert!(blinker.step());
+        let mut cells: Vec<[isize; 2]> = blinker.live().copied().collect();
+        cells.sort();
+        assert_eq!(cells, vec![[1, 0], [1, 1], [1, 2]]);
+
+        // Day 17's example.
+        let slice = ".#.\n..#\n###";
+        let mut cubes = life(Moore::<3>::new(), "B3/S23", &picture::<3>(slice, '#'));
+        let mut hypercubes = life(Moore::<4>::new(), "B3/S23", &picture::<4>(slice, '#'));
+        for _ in 0..6 {
+            cubes.step();
+            hypercubes.step();
+        }
+        assert_eq!(cubes.len(), 112);
+        assert_eq!(hypercubes.len(), 848);
+    }
+
+    #[test]
+    fn test_von_neumann() {
+        // With no survivors, a lone cell becomes the diamond around it.  Then only the cells
+        // straight out from those touch just one of them; the rest of the ring touches two.
+        let mut dot = life(VonNeumann::<2>, "B1/S", &[[0, 0]]);
+        dot.step();
+        assert_eq!(dot.len(), 4);
+        assert!(!dot.contains(&[0, 0]));
+        dot.step();
+        assert_eq!(dot.len(), 4);
+        assert!(dot.contains(&[2, 0]) && !dot.contains(&[1, 1]));
+
+        // With S0, cells with no neighbours at all still survive.
+        let mut pair = life(VonNeumann::<2>, "B/S0", &[[0, 0], [5, 5]]);
+        assert!(!pair.step());
+        assert_eq!(pair.len(), 2);
+    }
+
+    #[test]
+    fn test_hex() {
+        // Day 24's rules: a tile with two black neighbours flips to black, and a black one
+        // stays black with one or two.  In a line of three, each pair of neighbours turns the
+        // two tiles they both touch black.
+        let tile = |q, r| hexgrid::Hex::new(q, r);
+        let mut line = life(Hex, "B2/S12", &[tile(-1, 0), tile(0, 0), tile(1, 0)]);
+        line.step();
+        assert_eq!(line.len(), 7);
+        for cell in [tile(-1, 1), tile(0, -1), tile(0, 1), tile(1, -1)] {
+            assert!(line.contains(&cell));
+        }
+        assert!(!line.contains(&tile(1, 1)));
+    }
+
+    #[test]
+    fn test_bounded() {
+        // Day 11's first part: empty seats with no one next to them fill up, and people leave
+        // seats with 4 or more neighbours.  Floor isn't a cell at all.
+        let seats = "L.LL.LL.LL\n\
+                     LLLLLLL.LL\n\
+                     L.L.L..L..\n\
+                     LLLL.LL.LL\n\
+                     L.LL.LL.LL\n\
+                     L.LLLLL.LL\n\
+                     ..L.L.....\n\
+                     LLLLLLLLLL\n\
+                     L.LLLLLL.L\n\
+                     L.LLLLL.LL";
+        let rules = Rules::parse("B0/S0123").unwrap();
+        let mut room: Automaton<Moore<2>> =
+            Automaton::bounded(Moore::new(), rules, picture::<2>(seats, 'L'));
+        assert!(room.is_empty());
+        assert_eq!(room.settle(), 5);
+        assert_eq!(room.len(), 37);
+    }
+
+    #[test]
+    #[should_panic(expected = "B0")]
+    fn test_unbounded_b0() {
+        life(Hex, "B0/S", &[]);
+    }
+}
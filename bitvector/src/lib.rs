@@ -0,0 +1,387 @@
+// A fixed number of bits, packed 64 to a word.  It doubles as a set of the integers 0..len():
+// `&`, `|`, `^` and `!` are intersection, union, symmetric difference and complement, and the
+// shifts move every member up or down, dropping those that fall off either end.
+//
+// I rolled my own BitVector class after having difficulty with the BitVec crate.  In particular, I
+// kept using BitVec::splice to attempt to set a single bit, and BitVec::splice kept performing
+// unexpected shifts on my bit vector.  Instead of diving deeper, I threw away BitVec and rewrote
+// it in about 10 minutes.
+//
+// Getting back to BitVec now, I think I see the problem.  I get the impression that BitVec::splice
+// was never meant to be a public interface, and contains ominous warnings such as "It is
+// unspecified how many bits are removed from the vector if the Splice value is leaked."  While I
+// thought BitVec::splice was performing the splice operation, it now seems that it was returning
+// an iterator that had to be drained for splice to complete.  Muy confusing!
+//
+// It turns out that what I really wanted was to get a mutable BitSlice object that covered my
+// BitVec vector, and that the BitSlice object would provide the set method I was hoping for:
+//
+//    fn set(&mut self, index: usize, value: bool) {
+//       self.bits.as_mut_bitslice().set(index, value);
+//    }
+//
+// Ah well.
+
+use std::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Bound, Index, Not, RangeBounds,
+    Shl, Shr,
+};
+
+// Bits past `len` in the last word are always 0, so whole words can be counted and compared.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitVector {
+    data: Vec<u64>,
+    len: usize,
+}
+
+impl BitVector {
+    pub fn new(bits: usize) -> BitVector {
+        let words: usize = bits.div_ceil(64);
+        let mut bv = BitVector {
+            data: Vec::new(),
+            len: bits,
+        };
+        bv.data.resize(words, 0);
+        return bv;
+    }
+
+    // All `bits` bits set.
+    pub fn full(bits: usize) -> BitVector {
+        return !&BitVector::new(bits);
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, index: usize) -> bool {
+        let word = index / 64;
+        let mask: u64 = 1 << (index % 64);
+        if word >= self.data.len() {
+            return false;
+        }
+        return self.data[word] & mask != 0;
+    }
+
+    pub fn set(&mut self, index: usize, value: bool) {
+        assert!(index < self.len, "bit {} of {}", index, self.len);
+        let word = index / 64;
+        let mask: u64 = 1 << (index % 64);
+        let mut data: u64 = self.data[word];
+        data &= !mask;
+        data |= if value { mask } else { 0 };
+        self.data[word] = data;
+    }
+
+    // The start and end of `range`, which must lie within 0..len().
+    fn bounds(&self, range: impl RangeBounds<usize>) -> (usize, usize) {
+        let start = match range.start_bound() {
+            Bound::Included(&s) => s,
+            Bound::Excluded(&s) => s + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&e) => e + 1,
+            Bound::Excluded(&e) => e,
+            Bound::Unbounded => self.len,
+        };
+        assert!(
+            start <= end && end <= self.len,
+            "{}..{} of {}",
+            start,
+            end,
+            self.len
+        );
+        return (start, end);
+    }
+
+    // Each word that `range` touches, and a mask of the bits in it that are in the range.
+    fn words(&self, range: impl RangeBounds<usize>) -> impl Iterator<Item = (usize, u64)> {
+        let (start, end) = self.bounds(range);
+        let first = start / 64;
+        let last = end.div_ceil(64);
+        (first..last).map(move |word| {
+            let low = start.max(word * 64) - word * 64;
+            let high = end.min(word * 64 + 64) - word * 64;
+            let mask = match high - low {
+                64 => !0,
+                n => ((1u64 << n) - 1) << low,
+            };
+            (word, mask)
+        })
+    }
+
+    // How many bits in `range` are set, a word at a time.
+    pub fn count_ones(&self, range: impl RangeBounds<usize>) -> usize {
+        return self
+            .words(range)
+            .map(|(word, mask)| (self.data[word] & mask).count_ones() as usize)
+            .sum();
+    }
+
+    pub fn count_all_ones(&self) -> usize {
+        return self.data.iter().map(|&x| x.count_ones() as usize).sum();
+    }
+
+    pub fn set_range(&mut self, range: impl RangeBounds<usize>, value: bool) {
+        for (word, mask) in self.words(range).collect::<Vec<_>>() {
+            match value {
+                true => self.data[word] |= mask,
+                false => self.data[word] &= !mask,
+            }
+        }
+    }
+
+    // The lowest set bit, if any.
+    pub fn first_one(&self) -> Option<usize> {
+        return self
+            .data
+            .iter()
+            .position(|&word| word != 0)
+            .map(|word| word * 64 + self.data[word].trailing_zeros() as usize);
+    }
+
+    pub fn iter(&self) -> BitVectorIterator<'_> {
+        return BitVectorIterator::new(self.data.iter());
+    }
+
+    // Clear the bits past len() that an operation may have set.
+    fn trim(&mut self) {
+        let extra = self.len % 64;
+        if extra > 0 {
+            let last = self.data.len() - 1;
+            self.data[last] &= (1 << extra) - 1;
+        }
+    }
+
+    fn zip_with(&mut self, other: &BitVector, f: impl Fn(u64, u64) -> u64) {
+        assert_eq!(self.len, other.len, "bit vectors of different lengths");
+        for (a, b) in self.data.iter_mut().zip(other.data.iter()) {
+            *a = f(*a, *b);
+        }
+    }
+}
+
+impl Index<usize> for BitVector {
+    type Output = bool;
+
+    fn index(&self, index: usize) -> &bool {
+        assert!(index < self.len, "bit {} of {}", index, self.len);
+        match self.get(index) {
+            true => &true,
+            false => &false,
+        }
+    }
+}
+
+impl BitAndAssign<&BitVector> for BitVector {
+    fn bitand_assign(&mut self, other: &BitVector) {
+        self.zip_with(other, |a, b| a & b);
+    }
+}
+
+impl BitOrAssign<&BitVector> for BitVector {
+    fn bitor_assign(&mut self, other: &BitVector) {
+        self.zip_with(other, |a, b| a | b);
+    }
+}
+
+impl BitXorAssign<&BitVector> for BitVector {
+    fn bitxor_assign(&mut self, other: &BitVector) {
+        self.zip_with(other, |a, b| a ^ b);
+    }
+}
+
+impl BitAnd for &BitVector {
+    type Output = BitVector;
+
+    fn bitand(self, other: &BitVector) -> BitVector {
+        let mut result = self.clone();
+        result &= other;
+        return result;
+    }
+}
+
+impl BitOr for &BitVector {
+    type Output = BitVector;
+
+    fn bitor(self, other: &BitVector) -> BitVector {
+        let mut result = self.clone();
+        result |= other;
+        return result;
+    }
+}
+
+impl BitXor for &BitVector {
+    type Output = BitVector;
+
+    fn bitxor(self, other: &BitVector) -> BitVector {
+        let mut result = self.clone();
+        result ^= other;
+        return result;
+    }
+}
+
+impl Not for &BitVector {
+    type Output = BitVector;
+
+    fn not(self) -> BitVector {
+        let mut result = BitVector {
+            data: self.data.iter().map(|&x| !x).collect(),
+            len: self.len,
+        };
+        result.trim();
+        return result;
+    }
+}
+
+// Bit i moves to i + n.
+impl Shl<usize> for &BitVector {
+    type Output = BitVector;
+
+    fn shl(self, n: usize) -> BitVector {
+        let (words, bits) = (n / 64, n % 64);
+        let mut result = BitVector::new(self.len);
+        for i in words..self.data.len() {
+            let src = i - words;
+            result.data[i] = self.data[src] << bits;
+            if bits > 0 && src > 0 {
+                result.data[i] |= self.data[src - 1] >> (64 - bits);
+            }
+        }
+        result.trim();
+        return result;
+    }
+}
+
+// Bit i moves to i - n.
+impl Shr<usize> for &BitVector {
+    type Output = BitVector;
+
+    fn shr(self, n: usize) -> BitVector {
+        let (words, bits) = (n / 64, n % 64);
+        let mut result = BitVector::new(self.len);
+        for i in 0..self.data.len().saturating_sub(words) {
+            let src = i + words;
+            result.data[i] = self.data[src] >> bits;
+            if bits > 0 && src + 1 < self.data.len() {
+                result.data[i] |= self.data[src + 1] << (64 - bits);
+            }
+        }
+        return result;
+    }
+}
+
+pub struct BitVectorIterator<'a> {
+    it: std::slice::Iter<'a, u64>,
+    current: Option<&'a u64>,
+    word: isize,
+    bit: usize,
+}
+
+impl<'a> BitVectorIterator<'a> {
+    fn new(it: std::slice::Iter<'a, u64>) -> BitVectorIterator<'a> {
+        BitVectorIterator {
+            it,
+            current: Option::None,
+            word: -1,
+            bit: 0,
+        }
+    }
+
+    fn advance_to_next_word(&mut self) -> bool {
+        self.current = self.it.next();
+        self.word += 1;
+        self.bit = 0;
+        return self.current.is_none();
+    }
+}
+
+impl<'a> Iterator for BitVectorIterator<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        loop {
+            if self.current.is_none() && self.advance_to_next_word() {
+                return Option::None;
+            }
+            // skip all zero values:
+            while *self.current.unwrap() == 0 {
+                if self.advance_to_next_word() {
+                    return Option::None;
+                }
+            }
+            let value: u64 = *self.current.unwrap();
+            while self.bit < 64 {
+                if (value & (1 << self.bit)) != 0 {
+                    let index: usize = self.word as usize * 64 + self.bit;
+                    self.bit += 1;
+                    return Option::Some(index);
+                }
+                self.bit += 1;
+            }
+            // we've arrived at bit 32, so throw our data away.
+            self.current = Option::None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bits(len: usize, ones: &[usize]) -> BitVector {
+        let mut bv = BitVector::new(len);
+        for &i in ones {
+            bv.set(i, true);
+        }
+        return bv;
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        let a = bits(100, &[1, 2, 70]);
+        let b = bits(100, &[2, 3, 70, 99]);
+        assert_eq!(&a & &b, bits(100, &[2, 70]));
+        assert_eq!(&a | &b, bits(100, &[1, 2, 3, 70, 99]));
+        assert_eq!(&a ^ &b, bits(100, &[1, 3, 99]));
+        let not = !&a;
+        assert_eq!(not.count_all_ones(), 97);
+        assert!(!not[70] && not[99]);
+        assert_eq!(BitVector::full(100).count_all_ones(), 100);
+        let mut c = a.clone();
+        c &= &!&b;
+        assert_eq!(c.iter().collect::<Vec<_>>(), [1]);
+        assert_eq!(c.first_one(), Some(1));
+        assert_eq!(BitVector::new(10).first_one(), None);
+    }
+
+    #[test]
+    fn test_shifts() {
+        let a = bits(130, &[0, 63, 64, 129]);
+        assert_eq!(&a << 1, bits(130, &[1, 64, 65]));
+        assert_eq!(&a << 65, bits(130, &[65, 128, 129]));
+        assert_eq!(&a >> 1, bits(130, &[62, 63, 128]));
+        assert_eq!(&a >> 64, bits(130, &[0, 65]));
+        assert_eq!(&a >> 200, BitVector::new(130));
+        assert_eq!(&a << 0, a);
+    }
+
+    #[test]
+    fn test_ranges() {
+        let mut a = BitVector::new(200);
+        a.set_range(10..150, true);
+        assert_eq!(a.count_all_ones(), 140);
+        a.set_range(64..=127, false);
+        assert_eq!(a.count_ones(..), 76);
+        assert_eq!(a.count_ones(0..64), 54);
+        assert_eq!(a.count_ones(60..70), 4);
+        assert_eq!(a.count_ones(128..), 22);
+        assert!(a[10] && !a[9] && !a[100]);
+        assert_eq!(a.len(), 200);
+    }
+}
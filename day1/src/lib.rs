@@ -0,0 +1,59 @@
+use advent::{Error, Solution};
+
+// Find the two entries that sum to 2020 and return their product.
+fn find_pair(nums: &[i64]) -> i64 {
+    for i in 0..nums.len() {
+        for j in (i + 1)..nums.len() {
+            if nums[i] + nums[j] == 2020 {
+                eprintln!("{} {} {}", nums[i], nums[j], nums[i] * nums[j]);
+                return nums[i] * nums[j];
+            }
+        }
+    }
+    panic!("no pair of entries sums to 2020");
+}
+
+// Find the three entries that sum to 2020 and return their product.
+fn find_triple(nums: &[i64]) -> i64 {
+    for i in 0..nums.len() {
+        for j in (i + 1)..nums.len() {
+            for k in (j + 1)..nums.len() {
+                if nums[i] + nums[j] + nums[k] == 2020 {
+                    eprintln!(
+                        "{}:{} {}:{} {}:{} = {}",
+                        i,
+                        nums[i],
+                        j,
+                        nums[j],
+                        k,
+                        nums[k],
+                        nums[i] * nums[j] * nums[k]
+                    );
+                    return nums[i] * nums[j] * nums[k];
+                }
+            }
+        }
+    }
+    panic!("no triple of entries sums to 2020");
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u32 = 1;
+    type Input<'a> = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(text: &str) -> Result<Vec<i64>, Error> {
+        advent::parse_lines(text, |x| advent::parse_number(x, 1))
+    }
+
+    fn part1(nums: &Vec<i64>) -> i64 {
+        find_pair(nums)
+    }
+
+    fn part2(nums: &Vec<i64>) -> i64 {
+        find_triple(nums)
+    }
+}
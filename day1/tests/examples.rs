@@ -0,0 +1,4 @@
+advent::examples! {
+    day1::Puzzle;
+    example: include_str!("../example.txt") => part1 = 514579, part2 = 241861950;
+}
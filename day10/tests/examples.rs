@@ -0,0 +1,5 @@
+advent::examples! {
+    day10::Puzzle;
+    small: "16\n10\n15\n5\n1\n11\n7\n19\n6\n12\n4" => part1 = 35, part2 = 8;
+    large: include_str!("../example.txt") => part1 = 220, part2 = 19208;
+}
@@ -0,0 +1,421 @@
+use advent::{Error, Solution};
+
+// Which seats a seat pays attention to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Neighbourhood {
+    // All eight directions, diagonals included.
+    Moore,
+    // Just up, down, left and right.
+    VonNeumann,
+}
+
+impl Neighbourhood {
+    fn directions(self) -> &'static [(i32, i32)] {
+        match self {
+            Neighbourhood::Moore => &[
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+                (0, -1),
+                (0, 1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+            ],
+            Neighbourhood::VonNeumann => &[(-1, 0), (0, -1), (0, 1), (1, 0)],
+        }
+    }
+}
+
+// How people choose their seats.  Each round, an empty seat fills up if none of the seats its
+// would-be occupant can see are occupied, and an occupied seat empties if `tolerance` or more
+// of them are.  Along each direction they see the first seat within `range` places, looking past
+// the floor; with no range, they can see all the way across the room.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Policy {
+    pub neighbourhood: Neighbourhood,
+    pub range: Option<usize>,
+    pub tolerance: usize,
+    // What the map uses for floor, an empty seat and an occupied seat.
+    pub floor: u8,
+    pub empty: u8,
+    pub occupied: u8,
+}
+
+impl Policy {
+    // Part 1: people only mind the seats right next to them, and leave if 4 are taken.
+    pub fn part1() -> Policy {
+        Policy {
+            neighbourhood: Neighbourhood::Moore,
+            range: Some(1),
+            tolerance: 4,
+            floor: b'.',
+            empty: b'L',
+            occupied: b'#',
+        }
+    }
+
+    // Part 2: people look as far as the first seat in each direction, and put up with 4.
+    pub fn part2() -> Policy {
+        Policy {
+            range: None,
+            tolerance: 5,
+            ..Policy::part1()
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SeatMap {
+    seats: Vec<u8>,
+    width: i32,
+    height: i32,
+}
+
+impl SeatMap {
+    fn new() -> SeatMap {
+        SeatMap {
+            seats: Vec::new(),
+            width: 0,
+            height: 0,
+        }
+    }
+
+    fn parse(&mut self, text: &str, policy: &Policy) -> Result<(), Error> {
+        let cells = [policy.floor, policy.empty, policy.occupied];
+        self.seats = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let l = line.trim();
+            if l == "" {
+                continue;
+            };
+            if let Some(j) = l.bytes().position(|b| !cells.contains(&b)) {
+                let msg = format!(
+                    "expected {:?}, {:?} or {:?}, found {:?}",
+                    policy.floor as char,
+                    policy.empty as char,
+                    policy.occupied as char,
+                    l[j..].chars().next().unwrap()
+                );
+                return Err(Error::at(i + 1, advent::column_of(line, l) + j, msg));
+            }
+            let bytes = l.as_bytes();
+            self.seats.extend_from_slice(&bytes);
+            if self.width == 0 {
+                self.width = l.len() as i32;
+            } else if l.len() as i32 != self.width {
+                return Err(Error::at(i + 1, 1, "rows must all be the same width"));
+            }
+            self.height += 1;
+        }
+        return Ok(());
+    }
+
+    #[cfg(test)]
+    fn pretty_print(&self) {
+        for row in 0..self.height {
+            eprint!("{}: ", row);
+            for col in 0..self.width {
+                let x = self.get(row, col);
+                eprint!("{}", x as char);
+            }
+            eprintln!("");
+        }
+    }
+
+    fn get(&self, row: i32, col: i32) -> u8 {
+        return self.seats[self.index(row, col)];
+    }
+
+    fn index(&self, row: i32, col: i32) -> usize {
+        return (row * self.width + col) as usize;
+    }
+
+    fn contains(&self, row: i32, col: i32) -> bool {
+        return (row >= 0) && (col >= 0) && (row < self.height) && (col < self.width);
+    }
+
+    // The map index of every seat that someone at (row, col) can see.
+    fn visible(&self, row: i32, col: i32, policy: &Policy) -> Vec<usize> {
+        let mut visible = Vec::new();
+        for &(deltarow, deltacol) in policy.neighbourhood.directions() {
+            let (mut r, mut c) = (row, col);
+            let mut steps = 0;
+            while policy.range.is_none_or(|range| steps < range) {
+                r += deltarow;
+                c += deltacol;
+                steps += 1;
+                if !self.contains(r, c) {
+                    break;
+                }
+                if self.get(r, c) != policy.floor {
+                    visible.push(self.index(r, c));
+                    break;
+                }
+            }
+        }
+        return visible;
+    }
+
+    // How many occupied seats someone at (row, col) can see.
+    #[cfg(test)]
+    fn count_visible_occupants(&self, row: i32, col: i32, policy: &Policy) -> usize {
+        return self
+            .visible(row, col, policy)
+            .iter()
+            .filter(|&&i| self.seats[i] == policy.occupied)
+            .count();
+    }
+
+    // The map as it was parsed: '.' for floor, 'L' for an empty seat, '#' for an occupied one.
+    fn to_text(&self) -> String {
+        let rows: Vec<String> = self
+            .seats
+            .chunks(self.width.max(1) as usize)
+            .map(|row| String::from_utf8_lossy(row).into_owned())
+            .collect();
+        return rows.join("\n");
+    }
+}
+
+// A seat map being filled under a Policy.  Who can see whom never changes, so each seat's list of
+// visible seats is worked out once, up front, and each round just adds up who's sitting in them.
+pub struct Simulator {
+    map: SeatMap,
+    policy: Policy,
+    // The map index of each seat, by seat number.
+    seats: Vec<usize>,
+    // The seat numbers each seat can see.
+    visible: Vec<Vec<usize>>,
+    occupied: Vec<bool>,
+}
+
+impl Simulator {
+    pub fn new(map: &SeatMap, policy: Policy) -> Simulator {
+        let seats: Vec<usize> = (0..map.seats.len())
+            .filter(|&i| map.seats[i] != policy.floor)
+            .collect();
+        // Seat numbers by map index.
+        let mut number = vec![usize::MAX; map.seats.len()];
+        for (n, &i) in seats.iter().enumerate() {
+            number[i] = n;
+        }
+        let visible = seats
+            .iter()
+            .map(|&i| {
+                let (row, col) = (i as i32 / map.width, i as i32 % map.width);
+                let seen = map.visible(row, col, &policy);
+                seen.iter().map(|&j| number[j]).collect()
+            })
+            .collect();
+        let occupied = seats
+            .iter()
+            .map(|&i| map.seats[i] == policy.occupied)
+            .collect();
+        Simulator {
+            map: map.clone(),
+            policy,
+            seats,
+            visible,
+            occupied,
+        }
+    }
+
+    // Play one round, and return whether anyone moved.
+    pub fn step(&mut self) -> bool {
+        let counts: Vec<usize> = self
+            .visible
+            .iter()
+            .map(|seen| seen.iter().filter(|&&n| self.occupied[n]).count())
+            .collect();
+        let mut changed = false;
+        for (n, &count) in counts.iter().enumerate() {
+            let next = match self.occupied[n] {
+                true => count < self.policy.tolerance,
+                false => count == 0,
+            };
+            if next != self.occupied[n] {
+                self.occupied[n] = next;
+                self.map.seats[self.seats[n]] = match next {
+                    true => self.policy.occupied,
+                    false => self.policy.empty,
+                };
+                changed = true;
+            }
+        }
+        return changed;
+    }
+
+    // Play rounds until nobody moves, and return how many rounds anyone did.
+    pub fn run(&mut self) -> u32 {
+        let mut steps = 0u32;
+        while self.step() {
+            steps += 1
+        }
+        return steps;
+    }
+
+    pub fn count_occupied(&self) -> usize {
+        return self.occupied.iter().filter(|&&o| o).count();
+    }
+
+    // The map as it stands.
+    pub fn to_text(&self) -> String {
+        return self.map.to_text();
+    }
+}
+
+#[cfg(test)]
+fn visibility_test(text: &str, row: i32, col: i32, visible: usize) {
+    let mut seatmap_visibility_test1 = SeatMap::new();
+    seatmap_visibility_test1
+        .parse(text, &Policy::part1())
+        .unwrap();
+    seatmap_visibility_test1.pretty_print();
+    assert_eq!(
+        seatmap_visibility_test1.count_visible_occupants(row, col, &Policy::part2()),
+        visible
+    );
+    eprintln!("ok: {},{} can see {}", row, col, visible);
+}
+
+#[test]
+fn test_visibility() {
+    visibility_test(
+        r"
+      .##.##.
+      #.#.#.#
+      ##...##
+      ...L...
+      ##...##
+      #.#.#.#
+      .##.##.",
+        3,
+        3,
+        0,
+    );
+    visibility_test(
+        r"
+        .......#.
+        ...#.....
+        .#.......
+        .........
+        ..#L....#
+        ....#....
+        .........
+        #........
+        ...#.....",
+        4,
+        3,
+        8,
+    );
+    visibility_test(
+        r"
+    .............
+    .L.L.#.#.#.#.
+    .............",
+        1,
+        2,
+        0,
+    );
+    visibility_test(
+        r"
+    .............
+    .L.L.#.#.#.#.
+    .............",
+        1,
+        4,
+        1,
+    );
+}
+
+// The seats at the start and after each round until they settle, for drawing.
+pub fn generations(seatmap: &SeatMap, policy: Policy) -> Vec<String> {
+    let mut simulator = Simulator::new(seatmap, policy);
+    let mut generations = vec![simulator.to_text()];
+    while simulator.step() {
+        generations.push(simulator.to_text());
+    }
+    return generations;
+}
+
+#[test]
+fn test_generations() {
+    let seatmap = Puzzle::parse(include_str!("../example.txt")).unwrap();
+    let generations = generations(&seatmap, Policy::part1());
+    assert_eq!(generations[0], include_str!("../example.txt").trim());
+    assert_eq!(generations.len(), 6);
+    assert_eq!(generations[5].matches('#').count(), 37);
+}
+
+#[test]
+fn test_policies() {
+    let seatmap = Puzzle::parse(include_str!("../example.txt")).unwrap();
+    let settle = |policy: Policy| {
+        let mut simulator = Simulator::new(&seatmap, policy);
+        simulator.run();
+        return simulator.count_occupied();
+    };
+    assert_eq!(settle(Policy::part1()), 37);
+    assert_eq!(settle(Policy::part2()), 26);
+    // With only four neighbours, nobody ever sees 5 occupied seats, so everyone sits down in the
+    // first round and stays there.
+    let straight = Policy {
+        neighbourhood: Neighbourhood::VonNeumann,
+        tolerance: 5,
+        ..Policy::part1()
+    };
+    let mut simulator = Simulator::new(&seatmap, straight);
+    assert_eq!(simulator.run(), 1);
+    assert_eq!(simulator.count_occupied(), simulator.seats.len());
+
+    // Other characters for the map.
+    let boxes = Policy {
+        floor: b'_',
+        empty: b'o',
+        occupied: b'x',
+        ..Policy::part1()
+    };
+    let text = include_str!("../example.txt")
+        .replace('.', "_")
+        .replace('L', "o");
+    let mut seatmap = SeatMap::new();
+    seatmap.parse(&text, &boxes).unwrap();
+    let mut simulator = Simulator::new(&seatmap, boxes.clone());
+    simulator.run();
+    assert_eq!(simulator.count_occupied(), 37);
+    assert!(simulator.to_text().contains('x'));
+    let mut seatmap = SeatMap::new();
+    let error = seatmap.parse("L.#", &boxes).unwrap_err().to_string();
+    assert_eq!(
+        error,
+        "line 1, column 1: expected '_', 'o' or 'x', found 'L'"
+    );
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u32 = 11;
+    type Input<'a> = SeatMap;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(text: &str) -> Result<SeatMap, Error> {
+        let mut seatmap = SeatMap::new();
+        seatmap.parse(text, &Policy::part1())?;
+        Ok(seatmap)
+    }
+
+    fn part1(seatmap: &SeatMap) -> u32 {
+        let mut simulator = Simulator::new(seatmap, Policy::part1());
+        dbg!(simulator.run());
+        simulator.count_occupied() as u32
+    }
+
+    fn part2(seatmap: &SeatMap) -> u32 {
+        let mut simulator = Simulator::new(seatmap, Policy::part2());
+        dbg!(simulator.run());
+        simulator.count_occupied() as u32
+    }
+}
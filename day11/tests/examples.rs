@@ -0,0 +1,4 @@
+advent::examples! {
+    day11::Puzzle;
+    example: include_str!("../example.txt") => part1 = 37, part2 = 26;
+}
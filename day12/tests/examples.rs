@@ -0,0 +1,4 @@
+advent::examples! {
+    day12::Puzzle;
+    example: "F10\nN3\nF7\nR90\nF11" => part1 = 25, part2 = 286;
+}
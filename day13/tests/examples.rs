@@ -0,0 +1,9 @@
+advent::examples! {
+    day13::Puzzle;
+    example: "939\n7,13,x,x,59,x,31,19" => part1 = 295, part2 = 1068781;
+    schedule1: "939\n17,x,13,19" => part2 = 3417;
+    schedule2: "939\n67,7,59,61" => part2 = 754018;
+    schedule3: "939\n67,x,7,59,61" => part2 = 779210;
+    schedule4: "939\n67,7,x,59,61" => part2 = 1261476;
+    schedule5: "939\n1789,37,47,1889" => part2 = 1202161486;
+}
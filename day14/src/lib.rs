@@ -0,0 +1,228 @@
+#[macro_use]
+extern crate lazy_static;
+extern crate regex;
+
+use advent::{Error, Solution};
+use regex::Regex;
+use std::collections::HashMap;
+
+type DataType = u64;
+
+#[derive(Clone, Debug)]
+pub struct Mask {
+    ones_mask: DataType,
+    zeros_mask: DataType,
+    xlist: Vec<u8>,
+}
+
+impl Mask {
+    // Parse a mask such as "XX10X...", which starts at `column` of its line.
+    fn new(s: &str, column: usize) -> Result<Mask, Error> {
+        let mut m = Mask {
+            ones_mask: 0,
+            zeros_mask: 0,
+            xlist: Vec::new(),
+        };
+        if s.len() != 36 {
+            return Err(Error::parse(column, "expected a 36 bit mask"));
+        }
+        let mut bitpos: u8 = s.len() as u8;
+        for (i, ch) in s.chars().enumerate() {
+            bitpos -= 1;
+            m.ones_mask <<= 1;
+            m.zeros_mask <<= 1;
+            match ch {
+                '0' => m.zeros_mask |= 1, // mask = mask | 1;  mask = mask + 1
+                '1' => m.ones_mask |= 1,
+                'X' => m.xlist.push(bitpos),
+                _ => {
+                    let msg = format!("expected '0', '1' or 'X', found {:?}", ch);
+                    return Err(Error::parse(column + i, msg));
+                }
+            }
+        }
+        m.xlist.reverse(); // put the LSB first.
+        return Ok(m);
+    }
+
+    fn xmask(&self) -> DataType {
+        return !(self.ones_mask | self.zeros_mask);
+    }
+
+    // DataType is u64
+    fn apply(&self, w: DataType) -> DataType {
+        return (w | self.ones_mask) & !self.zeros_mask;
+    }
+
+    fn addr_iter(&self, a: DataType) -> MaskIterator {
+        MaskIterator::new(self.clone(), a)
+    }
+} // impl Mask
+
+// My first iterator!
+struct MaskIterator {
+    index: u64,
+    max: u64,
+    base: u64,
+    xlist: Vec<u8>,
+}
+
+impl MaskIterator {
+    fn new(m: Mask, a: u64) -> MaskIterator {
+        let mut it = MaskIterator {
+            index: 0,
+            max: 0,
+            base: a,
+            xlist: m.xlist.clone(),
+        };
+        it.base |= m.ones_mask;
+        it.base &= !m.xmask();
+        it.max = (1u64 << it.xlist.len()) - 1; // inclusive bound
+        return it;
+    }
+}
+
+impl Iterator for MaskIterator {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.index > self.max {
+            return None;
+        }
+        let mut a = self.base;
+        for bitpos in 0..self.xlist.len() {
+            if (self.index & (1 << bitpos)) != 0 {
+                a |= 1 << self.xlist[bitpos];
+            }
+        }
+        self.index += 1;
+        return Some(a);
+    }
+}
+
+struct Memory {
+    data: HashMap<u64, u64>,
+}
+
+impl Memory {
+    fn new() -> Memory {
+        Memory {
+            data: HashMap::new(),
+        }
+    }
+
+    fn get(&self, i: u64) -> u64 {
+        return self.data.get(&i).cloned().unwrap_or(0);
+    }
+
+    fn set(&mut self, i: u64, v: u64) {
+        self.data.insert(i, v);
+    }
+
+    fn sum(&self) -> u64 {
+        self.data.values().sum()
+    }
+
+    fn bigsum(&self) -> u128 {
+        self.data.values().map(|x| *x as u128).sum()
+    }
+}
+
+pub enum Instruction {
+    MaskOp(Mask),
+    Store(u64, u64),
+}
+
+impl Instruction {
+    fn new(s: &str) -> Result<Instruction, Error> {
+        lazy_static! {
+            static ref RE_STORE: Regex = Regex::new(r"^mem\[(\d+)\] = (\d+)$").unwrap();
+        }
+        if s.starts_with("mask = ") {
+            return Ok(Instruction::MaskOp(Mask::new(&s[7..], 8)?));
+        }
+        let caps = match RE_STORE.captures(s) {
+            Some(caps) => caps,
+            None => return Err(Error::parse(1, "expected \"mask = ...\" or \"mem[a] = v\"")),
+        };
+        let number = |i: usize| {
+            let m = caps.get(i).unwrap();
+            advent::parse_number::<u64>(m.as_str(), m.start() + 1)
+        };
+        return Ok(Instruction::Store(number(1)?, number(2)?));
+    }
+}
+
+struct System {
+    mask: Mask,
+    mem: Memory,
+}
+
+pub type Program = Vec<Instruction>;
+
+fn parse_program(content: &str) -> Result<Program, Error> {
+    advent::parse_lines(content, |line| Instruction::new(line.trim()))
+}
+
+impl System {
+    fn new() -> System {
+        System {
+            mask: Mask::new("000000000000000000000000000000000000", 1).unwrap(),
+            mem: Memory::new(),
+        }
+    }
+
+    fn run(&mut self, program: &Program) {
+        for instruction in program.iter() {
+            match instruction {
+                Instruction::MaskOp(m) => self.mask = m.clone(),
+                Instruction::Store(a, d) => self.store(*a, *d),
+            }
+        }
+    }
+
+    fn run_part2(&mut self, program: &Program) {
+        for instruction in program.iter() {
+            match instruction {
+                Instruction::MaskOp(m) => self.mask = m.clone(),
+                Instruction::Store(a, d) => self.store_part2(*a, *d),
+            }
+        }
+    }
+
+    fn store(&mut self, a: u64, d: u64) {
+        let v = self.mask.apply(d);
+        self.mem.set(a, v);
+    }
+
+    fn store_part2(&mut self, a: u64, d: u64) {
+        for address in self.mask.addr_iter(a) {
+            self.mem.set(address, d);
+        }
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u32 = 14;
+    type Input<'a> = Program;
+    type Answer1 = u64;
+    type Answer2 = u128;
+
+    fn parse(text: &str) -> Result<Program, Error> {
+        parse_program(text)
+    }
+
+    fn part1(program: &Program) -> u64 {
+        let mut system = System::new();
+        system.run(program);
+        system.mem.sum()
+    }
+
+    fn part2(program: &Program) -> u128 {
+        let mut system = System::new();
+        system.run_part2(program);
+        system.mem.bigsum()
+    }
+}
@@ -0,0 +1,5 @@
+advent::examples! {
+    day14::Puzzle;
+    example: include_str!("../example.txt") => part1 = 165;
+    floating: include_str!("../example2.txt") => part2 = 208;
+}
@@ -0,0 +1,74 @@
+// TODO: learn how to extend existing types (HashMap) using traits.
+use advent::{Error, Solution};
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::time::Instant;
+
+struct Tracker<S> {
+    seen: HashMap<u64, u64, S>,
+    turn: u64, // last turn
+}
+
+impl<S: BuildHasher + Default> Tracker<S> {
+    fn new() -> Tracker<S> {
+        Tracker {
+            seen: HashMap::default(),
+            turn: 0,
+        }
+    }
+
+    // returns the number of turns since number was added, or 0 if number is new.
+    fn append_number(&mut self, num: u64) -> u64 {
+        self.turn += 1;
+        let v = self.seen.insert(num, self.turn);
+        match v {
+            None => return 0,
+            Some(x) => return self.turn - x,
+        }
+    }
+}
+
+pub fn run(starters: Vec<u64>, until: u64) -> u64 {
+    run_with::<hashers::Std>(starters, until)
+}
+
+// The same, with `seen` hashed by S.
+pub fn run_with<S: BuildHasher + Default>(starters: Vec<u64>, until: u64) -> u64 {
+    let timer = Instant::now();
+    let mut tracker: Tracker<S> = Tracker::new();
+    let mut v: u64 = 0;
+    for num in starters {
+        v = tracker.append_number(num);
+    }
+    while tracker.turn < until - 1 {
+        v = tracker.append_number(v);
+    }
+    let elapsed = timer.elapsed();
+    eprintln!("completed {} steps in {:?}", until, elapsed);
+    return v;
+}
+
+// The puzzle, with the numbers seen so far hashed by S.  Puzzle hashes them with std's hasher.
+hashers::hashed!(Hashed, Puzzle);
+
+impl<S: BuildHasher + Default> Solution for Hashed<S> {
+    const DAY: u32 = 15;
+    type Input<'a> = Vec<u64>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(text: &str) -> Result<Vec<u64>, Error> {
+        let line = text.trim();
+        line.split(',')
+            .map(|x| advent::parse_number(x, advent::column_of(line, x)))
+            .collect()
+    }
+
+    fn part1(starters: &Vec<u64>) -> u64 {
+        run_with::<S>(starters.clone(), 2020)
+    }
+
+    fn part2(starters: &Vec<u64>) -> u64 {
+        run_with::<S>(starters.clone(), 30000000)
+    }
+}
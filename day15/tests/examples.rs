@@ -0,0 +1,21 @@
+use advent::Solution;
+use day15::Hashed;
+use hashers::Fx;
+
+advent::examples! {
+    day15::Puzzle;
+    example: "0,3,6" => part1 = 436;
+    start1: "1,3,2" => part1 = 1;
+    start2: "2,1,3" => part1 = 10;
+    start3: "1,2,3" => part1 = 27;
+    start4: "2,3,1" => part1 = 78;
+    start5: "3,2,1" => part1 = 438;
+    start6: "3,1,2" => part1 = 1836;
+}
+
+// The first example again, with the numbers seen so far hashed by fxhash.
+#[test]
+fn fx() {
+    let starters = Hashed::<Fx>::parse("0,3,6").unwrap();
+    assert_eq!(Hashed::<Fx>::part1(&starters), 436);
+}
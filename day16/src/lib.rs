@@ -0,0 +1,312 @@
+use advent::{Error, Solution};
+use bitvector::BitVector;
+use std::collections;
+
+#[derive(Copy, Clone, Debug, Default)]
+struct Range {
+    min: u32,
+    max: u32,
+}
+
+impl Range {
+    // Parse "min-max", which starts at `column` of its line.
+    fn new(text: &str, column: usize) -> Result<Range, Error> {
+        let p: Vec<&str> = text.split("-").collect();
+        if p.len() != 2 {
+            return Err(Error::parse(
+                column,
+                format!("expected min-max, found {:?}", text),
+            ));
+        }
+        Ok(Range {
+            min: advent::parse_number(p[0], column)?,
+            max: advent::parse_number(p[1], column + advent::column_of(text, p[1]) - 1)?,
+        })
+    }
+
+    fn check(&self, i: u32) -> bool {
+        (i >= self.min) && (i <= self.max)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct Field {
+    name: String,
+    id: usize, // the field's bit in a set of fields.
+    rules: Vec<Range>,
+}
+
+impl Field {
+    fn new(line: &str, id: usize) -> Result<Field, Error> {
+        let mut f = Field {
+            name: "".to_string(),
+            id,
+            rules: Vec::new(),
+        };
+        let p1: Vec<&str> = line.split(": ").collect();
+        if p1.len() != 2 {
+            return Err(Error::parse(1, "expected \"<name>: <range> or <range>\""));
+        }
+        f.name = p1[0].to_owned();
+        let p2: Vec<&str> = p1[1].split(" or ").collect();
+        for p in p2 {
+            f.rules.push(Range::new(p, advent::column_of(line, p))?);
+        }
+        return Ok(f);
+    }
+
+    fn check(&self, i: u32) -> bool {
+        for r in &self.rules {
+            if r.check(i) {
+                return true;
+            }
+        }
+        return false;
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct Matcher {
+    fields: Vec<Field>,
+    map: collections::HashMap<String, u32>,
+}
+
+impl Matcher {
+    fn new() -> Matcher {
+        Matcher {
+            fields: Vec::new(),
+            map: collections::HashMap::new(),
+        }
+    }
+
+    fn find_field(&self, value: u32) -> Option<String> {
+        for field in &self.fields {
+            if field.check(value) {
+                return Some(field.name.to_owned());
+            }
+        }
+        return None;
+    }
+
+    fn calc_error_rate(&self, value: u32) -> u32 {
+        if self.find_field(value).is_none() {
+            return value;
+        } else {
+            return 0u32;
+        }
+    }
+
+    fn bitmap_of_valid_fields(&self, value: u32) -> BitVector {
+        let mut bitmap = BitVector::new(self.fields.len());
+        for field in &self.fields {
+            if field.check(value) {
+                bitmap.set(field.id, true);
+            }
+        }
+        return bitmap;
+    }
+
+    fn parse(&mut self, text: &str) -> Result<(), Error> {
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            let field = Field::new(line, i).map_err(|e| e.below(i))?;
+            self.fields.push(field);
+        }
+        return Ok(());
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct Ticket {
+    values: Vec<u32>,
+}
+
+impl Ticket {
+    fn new(text: &str) -> Result<Ticket, Error> {
+        let values: Result<Vec<u32>, Error> = text
+            .split(",")
+            .map(|a| advent::parse_number(a, advent::column_of(text, a)))
+            .collect();
+        Ok(Ticket { values: values? })
+    }
+
+    fn calc_error_rate(&self, m: &Matcher) -> u32 {
+        let mut acc = 0u32;
+        for value in &self.values {
+            acc += m.calc_error_rate(*value);
+        }
+        return acc;
+    }
+
+    fn p1_is_valid(&self, m: &Matcher) -> bool {
+        for value in &self.values {
+            if m.find_field(*value).is_none() {
+                return false;
+            }
+        }
+        return true;
+    }
+}
+
+struct TicketDecoder {
+    field_id: Vec<BitVector>, // maps position to the fields it could be
+}
+
+// True if bitmap has at most one bit set.
+fn bitmap_has_one_bit_set(bitmap: &BitVector) -> bool {
+    return bitmap.count_all_ones() <= 1;
+}
+
+impl TicketDecoder {
+    fn new() -> TicketDecoder {
+        TicketDecoder {
+            field_id: Vec::new(),
+        }
+    }
+
+    fn id_to_position(&self, id: usize) -> u32 {
+        return self.field_id.iter().position(|x| x[id]).unwrap() as u32;
+    }
+
+    fn guess_fields(&mut self, matcher: &Matcher, tickets: &Vec<Ticket>) {
+        let positions = tickets[0].values.len() as u32;
+        self.field_id.clear();
+        for position in 0u32..positions {
+            let mut bitmask = BitVector::full(matcher.fields.len());
+            for ticket in tickets.iter().filter(|x| x.p1_is_valid(&matcher)) {
+                let value = ticket.values[position as usize];
+                bitmask &= &matcher.bitmap_of_valid_fields(value);
+            }
+            self.field_id.push(bitmask);
+        }
+        // Now use process of elimninatio to cull the bitmasks.
+        let mut iterations = 0;
+        loop {
+            iterations += 1;
+            dbg!(iterations);
+            let mut done: bool = true;
+            for position in 0u32..positions {
+                let bm = &self.field_id[position as usize];
+                if bitmap_has_one_bit_set(bm) {
+                    let others = !bm;
+                    for other_pos in 0u32..positions {
+                        if other_pos == position {
+                            continue;
+                        }
+                        self.field_id[other_pos as usize] &= &others;
+                    }
+                } else {
+                    done = false;
+                }
+            }
+            if done {
+                break;
+            }
+        }
+        for position in 0u32..positions {
+            let bitmask = &self.field_id[position as usize];
+            eprintln!(
+                "Position {} -> fields {:?}",
+                position,
+                bitmask.iter().collect::<Vec<_>>()
+            );
+            assert!(
+                bitmap_has_one_bit_set(bitmask),
+                "bitmask had more than one bit set!"
+            );
+        }
+    }
+}
+
+pub struct Notes {
+    matcher: Matcher,
+    my_ticket: Ticket,
+    tickets: Vec<Ticket>,
+}
+
+// The notes are three sections: the field rules, "your ticket:" and "nearby tickets:".
+fn parse_notes(text: &str) -> Result<Notes, Error> {
+    let parts: Vec<&str> = text.split("\n\n").collect();
+    if parts.len() != 3 {
+        return Err(Error::at(
+            1,
+            1,
+            "expected rules, your ticket and nearby tickets sections",
+        ));
+    }
+    let mut matcher = Matcher::new();
+    matcher.parse(parts[0])?;
+    dbg!(&matcher);
+
+    // Where the last two sections start.
+    let line1 = parts[0].lines().count() + 1;
+    let line2 = line1 + parts[1].lines().count() + 1;
+
+    if parts[1].lines().next() != Some("your ticket:") {
+        return Err(Error::at(line1 + 1, 1, "expected \"your ticket:\""));
+    }
+    let my_ticket = match parts[1].lines().nth(1) {
+        Some(line) => Ticket::new(line).map_err(|e| e.below(line1 + 1))?,
+        None => return Err(Error::at(line1 + 2, 1, "expected your ticket")),
+    };
+
+    if parts[2].lines().next() != Some("nearby tickets:") {
+        return Err(Error::at(line2 + 1, 1, "expected \"nearby tickets:\""));
+    }
+    let mut tickets: Vec<Ticket> = Vec::new();
+    for (i, line) in parts[2].lines().enumerate().skip(1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        tickets.push(Ticket::new(line).map_err(|e| e.below(line2 + i))?);
+    }
+    dbg!(&tickets);
+
+    return Ok(Notes {
+        matcher,
+        my_ticket,
+        tickets,
+    });
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u32 = 16;
+    type Input<'a> = Notes;
+    type Answer1 = u32;
+    type Answer2 = u64;
+
+    fn parse(text: &str) -> Result<Notes, Error> {
+        parse_notes(text)
+    }
+
+    fn part1(notes: &Notes) -> u32 {
+        let p1_valid_cnt = notes
+            .tickets
+            .iter()
+            .filter(|&x| x.p1_is_valid(&notes.matcher))
+            .count();
+        dbg!(p1_valid_cnt);
+        let mut p1_error_rate = 0u32;
+        for ticket in &notes.tickets {
+            p1_error_rate += ticket.calc_error_rate(&notes.matcher);
+        }
+        return p1_error_rate;
+    }
+
+    fn part2(notes: &Notes) -> u64 {
+        let mut decoder = TicketDecoder::new();
+        decoder.guess_fields(&notes.matcher, &notes.tickets);
+        let product: u64 = notes
+            .matcher
+            .fields
+            .iter()
+            .filter(|f| f.name.starts_with("departure"))
+            .map(|f| f.id)
+            .map(|id| decoder.id_to_position(id))
+            .map(|pos| notes.my_ticket.values[pos as usize] as u64)
+            .product();
+        return product;
+    }
+}
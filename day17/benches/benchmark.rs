@@ -6,7 +6,14 @@ fn part2_benchmark(c: &mut Criterion) {
     let contents = fs::read_to_string("input.txt").expect("Something went wrong reading the file");
 
     c.bench_function("part2", |b| b.iter(|| part2(&contents)));
+    for &kind in hashers::Kind::ALL.iter() {
+        let name = format!("hashed_part2/{}", kind);
+        hashers::with_hasher!(kind, |S| {
+            c.bench_function(&name, |b| b.iter(|| hashed_part2::<S>(&contents)))
+        });
+    }
     c.bench_function("bv_part2", |b| b.iter(|| bv_part2(&contents)));
+    c.bench_function("mirrored_part2", |b| b.iter(|| mirrored_part2(&contents)));
 }
 
 criterion_group!(benches, part2_benchmark);
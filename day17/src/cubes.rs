@@ -0,0 +1,403 @@
+// The pocket dimension, in any number of dimensions and under any birth/survival rules.
+//
+// A Grid holds the active cubes inside their bounding box.  Each cycle, every cube counts its
+// active neighbours: the 3^D - 1 cubes that differ from it by at most one along every axis.
+// Rules then say which inactive cubes become active (birth) and which active cubes stay active
+// (survival).  Cells is how the active cubes are stored, so the bit vector and the hash sets
+// can be compared with everything else the same.
+
+use bitvector::BitVector;
+
+// The active cubes of a Grid, by index.
+pub trait Cells {
+    // Room for indices 0..len, all inactive.
+    fn new(len: usize) -> Self;
+    fn get(&self, index: usize) -> bool;
+    fn set(&mut self, index: usize, value: bool);
+    fn count_all_ones(&self) -> usize;
+    // The index of every active cube.
+    fn ones(&self) -> Vec<usize>;
+}
+
+impl Cells for BitVector {
+    fn new(len: usize) -> BitVector {
+        BitVector::new(len)
+    }
+    fn get(&self, index: usize) -> bool {
+        BitVector::get(self, index)
+    }
+    fn set(&mut self, index: usize, value: bool) {
+        BitVector::set(self, index, value)
+    }
+    fn count_all_ones(&self) -> usize {
+        BitVector::count_all_ones(self)
+    }
+    fn ones(&self) -> Vec<usize> {
+        self.iter().collect()
+    }
+}
+
+// The birth/survival rules, which are shared with the other automata.
+pub use automaton::Rules;
+
+// The active cubes of a D dimensional pocket dimension, stored in C.
+//
+// A mirrored grid takes advantage of the puzzle's symmetry.  The starting slice lies at 0 on
+// every axis past the first two, so from then on, reflecting any of those axes (z to -z, w to
+// -w, ...) gives back the same pocket dimension.  It's enough to store the half of each of those
+// axes that's 0 or more, and the 2^k reflections of each stored cube, where k is how many of its
+// extra coordinates aren't 0, are active along with it.
+#[derive(Clone)]
+pub struct Grid<C, const D: usize> {
+    // The coordinates of the cube at index 0, the lowest corner of the bounding box.
+    origin: [isize; D],
+    // How far the bounding box reaches along each axis.
+    size: [usize; D],
+    cells: C,
+    mirrored: bool,
+}
+
+// How far apart neighbours along each axis are, when cubes are stored in order of their first
+// coordinate, then their second, and so on.
+fn strides<const D: usize>(size: &[usize; D]) -> [usize; D] {
+    let mut strides = [1; D];
+    for axis in 1..D {
+        strides[axis] = strides[axis - 1] * size[axis - 1];
+    }
+    return strides;
+}
+
+impl<C: Cells, const D: usize> Grid<C, D> {
+    // A grid with just these cubes active.
+    pub fn new(active: &[[isize; D]]) -> Grid<C, D> {
+        Grid::with_active(active, false)
+    }
+
+    fn with_active(active: &[[isize; D]], mirrored: bool) -> Grid<C, D> {
+        let mut origin = [0; D];
+        let mut size = [0; D];
+        if !active.is_empty() {
+            for axis in 0..D {
+                let low = active.iter().map(|c| c[axis]).min().unwrap();
+                let high = active.iter().map(|c| c[axis]).max().unwrap();
+                origin[axis] = low;
+                size[axis] = (high - low + 1) as usize;
+            }
+        }
+        let mut grid = Grid {
+            origin,
+            size,
+            cells: C::new(size.iter().product()),
+            mirrored,
+        };
+        for coord in active {
+            let index = grid.index(coord).unwrap();
+            grid.cells.set(index, true);
+        }
+        return grid;
+    }
+
+    // The puzzle's starting state: a slice of '#' (active) and '.' (inactive) cubes, which lies
+    // along the first two axes at 0 on all the others.
+    pub fn from_slice(text: &str) -> Grid<C, D> {
+        assert!(D >= 2, "a slice needs at least 2 dimensions");
+        let mut active = Vec::new();
+        for (y, line) in text.lines().enumerate() {
+            for (x, ch) in line.chars().enumerate() {
+                if ch == '#' {
+                    let mut coord = [0; D];
+                    coord[0] = x as isize;
+                    coord[1] = y as isize;
+                    active.push(coord);
+                }
+            }
+        }
+        return Grid::new(&active);
+    }
+
+    // The same, as a mirrored grid.
+    pub fn from_slice_mirrored(text: &str) -> Grid<C, D> {
+        let mut grid = Grid::from_slice(text);
+        grid.mirrored = true;
+        return grid;
+    }
+
+    pub fn is_mirrored(&self) -> bool {
+        self.mirrored
+    }
+
+    fn index(&self, coord: &[isize; D]) -> Option<usize> {
+        let strides = strides(&self.size);
+        let mut index = 0;
+        for axis in 0..D {
+            let offset = coord[axis] - self.origin[axis];
+            if offset < 0 || offset as usize >= self.size[axis] {
+                return None;
+            }
+            index += offset as usize * strides[axis];
+        }
+        return Some(index);
+    }
+
+    fn coord(&self, mut index: usize) -> [isize; D] {
+        let mut coord = self.origin;
+        for (c, size) in coord.iter_mut().zip(self.size.iter()) {
+            *c += (index % size) as isize;
+            index /= size;
+        }
+        return coord;
+    }
+
+    pub fn get(&self, coord: &[isize; D]) -> bool {
+        let mut coord = *coord;
+        if self.mirrored {
+            for c in coord.iter_mut().skip(2) {
+                *c = c.abs();
+            }
+        }
+        match self.index(&coord) {
+            Some(index) => self.cells.get(index),
+            None => false,
+        }
+    }
+
+    // How many cubes are active, counting the reflections a mirrored grid doesn't store.
+    pub fn count_active(&self) -> usize {
+        if !self.mirrored {
+            return self.cells.count_all_ones();
+        }
+        return self
+            .active()
+            .iter()
+            .map(|coord| 1 << coord.iter().skip(2).filter(|&&c| c != 0).count())
+            .sum();
+    }
+
+    // The active cubes that are stored: for a mirrored grid, just those that are 0 or more
+    // along every extra axis.
+    pub fn active(&self) -> Vec<[isize; D]> {
+        self.cells
+            .ones()
+            .into_iter()
+            .map(|i| self.coord(i))
+            .collect()
+    }
+
+    // Run one cycle.
+    //
+    // Only cubes next to an active one can change (the rules can't have B0), so the next grid
+    // is this one grown by a cube in every direction.  Each active cube adds one to the count of
+    // every neighbour, then the rules decide each cube in the grown grid, and the result is
+    // trimmed to the new bounding box.
+    //
+    // A mirrored grid doesn't grow below 0 on the extra axes.  Instead, along each of them, a
+    // cube at 1 counts twice towards its neighbours at 0, once for itself and once for its
+    // reflection at -1.  Its reflections further out are too far away to be anyone's neighbours.
+    //
+    // With the parallel feature, every cycle goes through par_step().
+    pub fn step(&self, rules: &Rules) -> Grid<C, D> {
+        self.next(rules, cfg!(feature = "parallel"))
+    }
+
+    // The same, with the grown grid split into slabs along the last axis (z in 3D, w in 4D) that
+    // are worked out in parallel on rayon's thread pool.
+    #[cfg(feature = "parallel")]
+    pub fn par_step(&self, rules: &Rules) -> Grid<C, D> {
+        self.next(rules, true)
+    }
+
+    fn next(&self, rules: &Rules, parallel: bool) -> Grid<C, D> {
+        assert!(
+            !rules.born(0),
+            "B0 would activate every cube in the infinite grid"
+        );
+        if self.cells.count_all_ones() == 0 {
+            return Grid::with_active(&[], self.mirrored);
+        }
+        let mut grown: Grid<BitVector, D> = Grid {
+            origin: self.origin.map(|o| o - 1),
+            size: self.size.map(|s| s + 2),
+            cells: BitVector::new(0),
+            mirrored: self.mirrored,
+        };
+        if self.mirrored {
+            for axis in 2..D {
+                if grown.origin[axis] < 0 {
+                    grown.origin[axis] = 0;
+                    grown.size[axis] -= 1;
+                }
+            }
+        }
+        let strides = strides(&grown.size);
+        let neighbours = 3usize.pow(D as u32);
+        let offsets: Vec<([isize; D], isize)> = (0..neighbours)
+            .filter(|&n| n != neighbours / 2)
+            .map(|n| {
+                let mut delta = [0; D];
+                let mut digits = n;
+                for d in delta.iter_mut() {
+                    *d = (digits % 3) as isize - 1;
+                    digits /= 3;
+                }
+                let offset = delta
+                    .iter()
+                    .zip(strides.iter())
+                    .map(|(d, stride)| d * *stride as isize)
+                    .sum();
+                (delta, offset)
+            })
+            .collect();
+
+        // Where each active cube is in the grown grid, in order.
+        let mut centers: Vec<(usize, [isize; D])> = self
+            .cells
+            .ones()
+            .into_iter()
+            .map(|index| {
+                let coord = self.coord(index);
+                (grown.index(&coord).unwrap(), coord)
+            })
+            .collect();
+        centers.sort_unstable();
+
+        // Look the rules up once per count rather than once per cube.
+        let table: Vec<[bool; 2]> = (0..neighbours)
+            .map(|count| [rules.born(count), rules.survives(count)])
+            .collect();
+
+        // The cubes between `start` and `end` in the grown grid that will be active.  Only the
+        // active cubes within `reach` of that range can be their neighbours.
+        let mirrored = self.mirrored;
+        let reach: usize = strides.iter().sum();
+        let slab = |start: usize, end: usize| -> Vec<[isize; D]> {
+            let mut counts = vec![0u16; end - start];
+            let mut was_active = vec![false; end - start];
+            let first = centers.partition_point(|(center, _)| center + reach < start);
+            for (center, coord) in centers[first..].iter() {
+                if *center >= end + reach {
+                    break;
+                }
+                if (start..end).contains(center) {
+                    was_active[center - start] = true;
+                }
+                'offsets: for (delta, offset) in &offsets {
+                    let mut weight = 1;
+                    if mirrored {
+                        for axis in 2..D {
+                            match (coord[axis], delta[axis]) {
+                                (0, -1) => continue 'offsets,
+                                (1, -1) => weight *= 2,
+                                _ => (),
+                            }
+                        }
+                    }
+                    let target = (*center as isize + offset) as usize;
+                    if (start..end).contains(&target) {
+                        counts[target - start] += weight;
+                    }
+                }
+            }
+            (0..end - start)
+                .filter(|&i| table[counts[i] as usize][was_active[i] as usize])
+                .map(|i| grown.coord(start + i))
+                .collect()
+        };
+
+        let len: usize = grown.size.iter().product();
+        let active = match parallel {
+            #[cfg(feature = "parallel")]
+            true => {
+                use rayon::prelude::*;
+                let layer = strides[D - 1];
+                (0..grown.size[D - 1])
+                    .into_par_iter()
+                    .flat_map_iter(|l| slab(l * layer, (l + 1) * layer))
+                    .collect()
+            }
+            _ => slab(0, len),
+        };
+        return Grid::with_active(&active, self.mirrored);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_life() {
+        // A blinker flips between a row and a column.
+        let blinker: Grid<BitVector, 2> = Grid::from_slice("...\n###\n...");
+        let next = blinker.step(&Rules::conway());
+        let mut active = next.active();
+        active.sort_unstable();
+        assert_eq!(active, [[1, 0], [1, 1], [1, 2]]);
+        assert_eq!(next.step(&Rules::conway()).active().len(), 3);
+
+        // A glider moves one cube diagonally every 4 generations.
+        let mut glider: Grid<BitVector, 2> = Grid::from_slice(".#.\n..#\n###");
+        for _ in 0..4 {
+            glider = glider.step(&Rules::conway());
+        }
+        assert!(glider.get(&[2, 1]) && glider.get(&[3, 2]) && glider.get(&[1, 3]));
+        assert_eq!(glider.count_active(), 5);
+
+        // Under B1/S1, a lone cube has no neighbours to survive with, but all 8 of its
+        // neighbours are born around the hole it leaves.
+        let dot: Grid<BitVector, 2> = Grid::new(&[[0, 0]]);
+        let next = dot.step(&Rules::parse("B1/S1").unwrap());
+        assert_eq!(next.count_active(), 8);
+        assert!(next.get(&[-1, -1]) && !next.get(&[0, 0]));
+
+        // Mirroring doesn't change anything in 2D, where there are no extra axes.
+        let glider: Grid<BitVector, 2> = Grid::from_slice_mirrored(".#.\n..#\n###");
+        assert_eq!(glider.step(&Rules::conway()).active().len(), 5);
+
+        // Nothing stays nothing.
+        let empty: Grid<BitVector, 2> = Grid::new(&[]);
+        assert_eq!(empty.step(&Rules::conway()).count_active(), 0);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_par_step() {
+        let rules = Rules::conway();
+        for &mirrored in &[false, true] {
+            let mut serial: Grid<BitVector, 4> = Grid::from_slice(".#.\n..#\n###");
+            serial.mirrored = mirrored;
+            let mut parallel = serial.clone();
+            for _ in 0..4 {
+                serial = serial.next(&rules, false);
+                parallel = parallel.par_step(&rules);
+                let mut expected = serial.active();
+                expected.sort_unstable();
+                let mut active = parallel.active();
+                active.sort_unstable();
+                assert_eq!(active, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mirrored() {
+        let slice = ".#.\n..#\n###";
+        let rules = Rules::parse("B3/S23").unwrap();
+        let mut full: Grid<BitVector, 4> = Grid::from_slice(slice);
+        let mut half: Grid<BitVector, 4> = Grid::from_slice_mirrored(slice);
+        for _ in 0..4 {
+            full = full.step(&rules);
+            half = half.step(&rules);
+            assert_eq!(half.count_active(), full.count_active());
+            let mut stored: Vec<_> = full
+                .active()
+                .into_iter()
+                .filter(|c| c[2] >= 0 && c[3] >= 0)
+                .collect();
+            stored.sort_unstable();
+            let mut active = half.active();
+            active.sort_unstable();
+            assert_eq!(active, stored);
+            assert!(full.active().iter().all(|c| half.get(c)));
+        }
+    }
+}
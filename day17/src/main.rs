@@ -1,8 +1,90 @@
-use day17::*;
-use std::fs;
+// day17 [--dimensions N] [--rules B3/S23] [--cycles N] [--full] [--frames path] [input.txt]
+//
+// With no options, solves both parts.  With any of them, runs the one simulation they describe
+// (by default 3 dimensions, the puzzle's rules and 6 cycles) and prints how many cubes are active.
+// Simulations only store the half of the extra dimensions that's 0 or more, and let reflections
+// stand for the rest; --full simulates all of it instead.  --frames draws every cycle of the
+// simulation, a slice at a time, as an animated GIF or a PNG or PPM file per cycle, according to
+// the path's extension.
+
+use advent::Source;
+use day17::cubes::Rules;
+use std::env;
+use std::path::PathBuf;
+use std::process;
+
+fn fail(msg: String) -> ! {
+    eprintln!("day 17: {}", msg);
+    process::exit(1);
+}
+
+fn number(option: &str, value: Option<String>) -> usize {
+    match value.map(|v| v.parse()) {
+        Some(Ok(n)) => n,
+        _ => fail(format!("{} needs a number", option)),
+    }
+}
 
 fn main() {
-    let contents = fs::read_to_string("input.txt").expect("Something went wrong reading the file");
-    dbg!(part1(&contents));
-    dbg!(part2(&contents));
+    let mut dims = None;
+    let mut rules = None;
+    let mut cycles = None;
+    let mut full = false;
+    let mut frames = None;
+    let mut source = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dimensions" => dims = Some(number(&arg, args.next())),
+            "--cycles" => cycles = Some(number(&arg, args.next())),
+            "--full" => full = true,
+            "--frames" => match args.next() {
+                Some(path) => frames = Some(PathBuf::from(path)),
+                None => fail("--frames needs a path, like cubes.gif".to_string()),
+            },
+            "--rules" => match args.next() {
+                Some(text) => match Rules::parse(&text) {
+                    Ok(r) if r.born(0) => {
+                        fail("--rules can't have B0, which would activate every cube".to_string())
+                    }
+                    Ok(r) => rules = Some(r),
+                    Err(e) => fail(format!("--rules {}", e.report(&text))),
+                },
+                None => fail("--rules needs a value, like B3/S23".to_string()),
+            },
+            _ if source.is_none() => source = Some(Source::from_arg(&arg)),
+            _ => fail(format!("unexpected argument {:?}", arg)),
+        }
+    }
+    if dims.is_none() && rules.is_none() && cycles.is_none() && !full && frames.is_none() {
+        advent::solve::<day17::Puzzle>();
+        return;
+    }
+
+    let dims = dims.unwrap_or(3);
+    if !day17::DIMENSIONS.contains(&dims) {
+        let (low, high) = day17::DIMENSIONS.into_inner();
+        fail(format!("--dimensions must be from {} to {}", low, high));
+    }
+    let rules = rules.unwrap_or_else(Rules::conway);
+    let source = source.unwrap_or_else(|| Source::default_for(17, "input.txt"));
+    let text = match source.read() {
+        Ok(text) => text,
+        Err(e) => fail(e.to_string()),
+    };
+    if let Some(path) = frames {
+        let frames = match day17::frames(&text, dims, &rules, cycles.unwrap_or(6), 4) {
+            Ok(frames) => frames,
+            Err(e) => fail(e.report(&text)),
+        };
+        match render::save(&path, &frames) {
+            Ok(files) => eprintln!("wrote {}", render::describe(&files)),
+            Err(e) => fail(format!("{}: {}", path.display(), e)),
+        }
+        return;
+    }
+    match day17::simulate(&text, dims, &rules, cycles.unwrap_or(6), !full) {
+        Ok(count) => println!("{} active cubes", count),
+        Err(e) => fail(e.report(&text)),
+    }
 }
@@ -0,0 +1,15 @@
+use advent::Solution;
+use day17::Hashed;
+use hashers::Fx;
+
+advent::examples! {
+    day17::Puzzle;
+    example: ".#.\n..#\n###" => part1 = 112, part2 = 848;
+}
+
+// Hashed<S> keeps the cubes in a HashSet rather than a BitVector.  Check it with fxhash.
+#[test]
+fn fx() {
+    let slice = Hashed::<Fx>::parse(".#.\n..#\n###").unwrap();
+    assert_eq!(Hashed::<Fx>::part1(&slice), 112);
+}
@@ -1,7 +1,8 @@
 #[cfg(test)]
 mod tests {
 
-    use day17::Voxels;
+    use day17::cubes::{Grid, Rules};
+    use day17::{bv_part2, mirrored_part2, run, simulate};
 
     use day17::bitvector::BitVector;
 
@@ -30,34 +31,64 @@ mod tests {
 
     #[test]
     pub fn test() {
-        let mut voxels = Voxels::new(128);
-        voxels.initialize(".#.\n..#\n###");
-        voxels.print();
-        dbg!(voxels.count_adjacent(0, 0, 0));
-        for y in -1..2 {
-            println!(
-                "{}{}{}",
-                voxels.count_bit(-1, y, 0),
-                voxels.count_bit(0, y, 0),
-                voxels.count_bit(1, y, 0)
-            );
+        let rules = Rules::conway();
+        let mut grid: Grid<BitVector, 3> = Grid::from_slice(".#.\n..#\n###");
+        assert!(grid.get(&[1, 0, 0]) && !grid.get(&[0, 0, 0]));
+        // The puzzle shows the first few cycles.
+        let mut counts = Vec::new();
+        for _ in 0..6 {
+            grid = grid.step(&rules);
+            counts.push(grid.count_active());
         }
-        for y in -2..3 {
-            println!(
-                "{}{}{}{}{}",
-                voxels.count_adjacent(-2, y, 0),
-                voxels.count_adjacent(-1, y, 0),
-                voxels.count_adjacent(0, y, 0),
-                voxels.count_adjacent(1, y, 0),
-                voxels.count_adjacent(2, y, 0)
-            );
+        assert_eq!(&counts[..3], [11, 21, 38]);
+        // The slice starts at z = 0, so everything above it is mirrored below.
+        for [x, y, z] in grid.active() {
+            assert!(grid.get(&[x, y, -z]));
         }
-        for i in 0..6 {
-            println!("=== step {} ===", i);
-            voxels = voxels.update();
-            voxels.print();
-            dbg!(voxels.count_all_ones());
+        assert_eq!(112, grid.count_active());
+    }
+
+    #[test]
+    pub fn test_mirrored() {
+        let slice = ".#.\n..#\n###";
+        let rules = Rules::conway();
+        assert_eq!(run::<BitVector, 3>(slice, &rules, 6, true), 112);
+        assert_eq!(run::<BitVector, 4>(slice, &rules, 6, true), 848);
+        for dims in 2..=5 {
+            assert_eq!(
+                simulate(slice, dims, &rules, 3, true).unwrap(),
+                simulate(slice, dims, &rules, 3, false).unwrap()
+            );
         }
-        assert_eq!(112, voxels.count_all_ones());
+        let text = std::fs::read_to_string("input.txt").unwrap();
+        assert_eq!(mirrored_part2(&text), bv_part2(&text));
+    }
+
+    #[test]
+    pub fn test_dimensions() {
+        let slice = ".#.\n..#\n###";
+        let rules = Rules::conway();
+        assert_eq!(run::<BitVector, 3>(slice, &rules, 6, false), 112);
+        assert_eq!(run::<BitVector, 4>(slice, &rules, 6, false), 848);
+        assert_eq!(simulate(slice, 4, &rules, 6, false).unwrap(), 848);
+        // simulate() just picks which run() to call.
+        assert_eq!(
+            simulate(slice, 5, &rules, 2, false).unwrap(),
+            run::<BitVector, 5>(slice, &rules, 2, false)
+        );
+        // In 2D, the example is a glider.
+        assert_eq!(simulate(slice, 2, &rules, 100, false).unwrap(), 5);
+        assert!(simulate("..x", 3, &rules, 6, false).is_err());
+    }
+
+    #[test]
+    pub fn test_frames() {
+        let slice = ".#.\n..#\n###";
+        let frames = day17::frames(slice, 3, &Rules::conway(), 2, 1).unwrap();
+        assert_eq!(frames.len(), 3);
+        // After 2 cycles, the cubes span z = -2..=2 in five slices, each 5 by 5 with a gap
+        // around it.
+        assert_eq!((frames[2].width(), frames[2].height()), (5 * 6 + 1, 7));
+        assert!(day17::frames("..x", 3, &Rules::conway(), 1, 1).is_err());
     }
 } // mod tests
@@ -0,0 +1,322 @@
+// Arithmetic expressions: a tokenizer, a syntax tree, and a Pratt parser that takes the
+// operators' precedence as a table, so each of the puzzle's rule sets is just a different table.
+
+use crate::numbers::Number;
+use advent::Error;
+use std::fmt;
+
+// Numbers keep their text, so each kind of Number can read literals as large as it holds.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Kind {
+    Number(String),
+    Operator(char),
+    Open,
+    Close,
+}
+
+// A token, and the column of the line it starts at.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Token {
+    pub kind: Kind,
+    pub column: usize,
+}
+
+pub fn tokenize(line: &str) -> Result<Vec<Token>, Error> {
+    let mut tokens = Vec::new();
+    let mut chars = line.char_indices().peekable();
+    while let Some((i, ch)) = chars.next() {
+        let kind = match ch {
+            ' ' => continue,
+            '(' => Kind::Open,
+            ')' => Kind::Close,
+            '+' | '-' | '*' => Kind::Operator(ch),
+            '0'..='9' => {
+                let mut end = i + 1;
+                while let Some((j, '0'..='9')) = chars.peek() {
+                    end = j + 1;
+                    chars.next();
+                }
+                Kind::Number(line[i..end].to_string())
+            }
+            _ => return Err(Error::parse(i + 1, format!("unexpected {:?}", ch))),
+        };
+        tokens.push(Token {
+            kind,
+            column: i + 1,
+        });
+    }
+    return Ok(tokens);
+}
+
+// Numbers and operators remember their column, for reporting overflow.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    Number {
+        text: String,
+        column: usize,
+    },
+    Negate {
+        column: usize,
+        operand: Box<Expr>,
+    },
+    Binary {
+        op: char,
+        column: usize,
+        lhs: Box<Expr>,
+        rhs: Box<Expr>,
+    },
+}
+
+impl Expr {
+    // Evaluate with numbers of type N.  If an operation overflows, the error points at it.
+    pub fn eval<N: Number>(&self) -> Result<N, Error> {
+        let (result, column, msg) = match self {
+            Expr::Number { text, column } => (N::parse(text), *column, text.to_string()),
+            Expr::Negate { column, operand } => {
+                let value: N = operand.eval()?;
+                let msg = format!("-{}", value);
+                (value.neg(), *column, msg)
+            }
+            Expr::Binary {
+                op,
+                column,
+                lhs,
+                rhs,
+            } => {
+                let (lhs, rhs): (N, N) = (lhs.eval()?, rhs.eval()?);
+                let result = match op {
+                    '+' => lhs.add(&rhs),
+                    '-' => lhs.sub(&rhs),
+                    '*' => lhs.mul(&rhs),
+                    _ => panic!("unknown operator {:?}", op),
+                };
+                (result, *column, format!("{} {} {}", lhs, op, rhs))
+            }
+        };
+        match result {
+            Some(value) => Ok(value),
+            None => Err(Error::parse(
+                column,
+                format!("{} overflows {}", msg, N::NAME),
+            )),
+        }
+    }
+}
+
+// Fully parenthesized, so the tree's shape is visible.
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expr::Number { text, .. } => write!(f, "{}", text),
+            Expr::Negate { operand, .. } => write!(f, "-{}", operand),
+            Expr::Binary { op, lhs, rhs, .. } => write!(f, "({} {} {})", lhs, op, rhs),
+        }
+    }
+}
+
+// How tightly each binary operator binds: higher binds tighter, and operators of equal
+// precedence go left to right.  An operator that isn't in the table is an error.
+pub type Precedence = [(char, u32)];
+
+// Part 1: everything left to right.
+pub const LEFT_TO_RIGHT: &Precedence = &[('+', 1), ('-', 1), ('*', 1)];
+
+// Part 2: addition and subtraction before multiplication.
+pub const ADDITION_FIRST: &Precedence = &[('+', 2), ('-', 2), ('*', 1)];
+
+// Unary minus binds tighter than any binary operator, so "-2 * 3" is "(-2) * 3".
+const PREFIX: u32 = u32::MAX;
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    next: usize,
+    precedence: &'a Precedence,
+    // The column just past the end of the line, for errors about what's missing there.
+    end: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.next)
+    }
+
+    fn column(&self) -> usize {
+        match self.peek() {
+            Some(token) => token.column,
+            None => self.end,
+        }
+    }
+
+    // A number, a negated operand, or a parenthesized expression.
+    fn operand(&mut self) -> Result<Expr, Error> {
+        let token = match self.peek() {
+            Some(token) => token,
+            None => return Err(Error::parse(self.end, "expected a number or '('")),
+        };
+        self.next += 1;
+        match &token.kind {
+            Kind::Number(text) => Ok(Expr::Number {
+                text: text.clone(),
+                column: token.column,
+            }),
+            Kind::Operator('-') => Ok(Expr::Negate {
+                column: token.column,
+                operand: Box::new(self.expression(PREFIX)?),
+            }),
+            Kind::Open => {
+                let e = self.expression(0)?;
+                match self.peek() {
+                    Some(Token {
+                        kind: Kind::Close, ..
+                    }) => {
+                        self.next += 1;
+                        Ok(e)
+                    }
+                    _ => Err(Error::parse(token.column, "unclosed '('")),
+                }
+            }
+            _ => Err(Error::parse(token.column, "expected a number or '('")),
+        }
+    }
+
+    // An expression whose operators all bind tighter than `min`.
+    fn expression(&mut self, min: u32) -> Result<Expr, Error> {
+        let mut lhs = self.operand()?;
+        loop {
+            let (op, column) = match self.peek() {
+                Some(Token {
+                    kind: Kind::Operator(op),
+                    column,
+                }) => (*op, *column),
+                Some(Token {
+                    kind: Kind::Close, ..
+                })
+                | None => break,
+                Some(_) => return Err(Error::parse(self.column(), "expected an operator")),
+            };
+            let power = match self.precedence.iter().find(|(o, _)| *o == op) {
+                Some((_, power)) => *power,
+                None => {
+                    return Err(Error::parse(
+                        column,
+                        format!("unsupported operator {:?}", op),
+                    ))
+                }
+            };
+            if power <= min {
+                break;
+            }
+            self.next += 1;
+            let rhs = self.expression(power)?;
+            lhs = Expr::Binary {
+                op,
+                column,
+                lhs: Box::new(lhs),
+                rhs: Box::new(rhs),
+            };
+        }
+        return Ok(lhs);
+    }
+}
+
+// Parse a line's tokens into a tree, with operators binding according to `precedence`.
+pub fn parse(line: &str, precedence: &Precedence) -> Result<Expr, Error> {
+    parse_tokens(&tokenize(line)?, line.len() + 1, precedence)
+}
+
+// The same, for a line that's already been tokenized.  `end` is the column past its end.
+pub fn parse_tokens(tokens: &[Token], end: usize, precedence: &Precedence) -> Result<Expr, Error> {
+    let mut parser = Parser {
+        tokens,
+        next: 0,
+        precedence,
+        end,
+    };
+    let e = parser.expression(0)?;
+    if let Some(token) = parser.peek() {
+        return Err(Error::parse(token.column, "unmatched ')'"));
+    }
+    return Ok(e);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn test_parse() {
+        let tree = |line: &str, precedence| parse(line, precedence).unwrap().to_string();
+        assert_eq!(tree("1 + 2 * 3", LEFT_TO_RIGHT), "((1 + 2) * 3)");
+        assert_eq!(tree("1 * 2 + 3", ADDITION_FIRST), "(1 * (2 + 3))");
+        assert_eq!(tree("10 - 2 - 3", ADDITION_FIRST), "((10 - 2) - 3)");
+        assert_eq!(tree("-2 * -(3 + 4)", LEFT_TO_RIGHT), "(-2 * -(3 + 4))");
+        assert_eq!(tree("((42))", LEFT_TO_RIGHT), "42");
+        assert_eq!(
+            parse("12 * 34 - 5", LEFT_TO_RIGHT)
+                .unwrap()
+                .eval::<i64>()
+                .unwrap(),
+            403
+        );
+        assert_eq!(
+            parse("2 * -3 + 10", ADDITION_FIRST)
+                .unwrap()
+                .eval::<i64>()
+                .unwrap(),
+            14
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |line: &str| parse(line, LEFT_TO_RIGHT).unwrap_err().to_string();
+        assert_eq!(error("1 + x"), "line 1, column 5: unexpected 'x'");
+        assert_eq!(error("1 + (2 * 3"), "line 1, column 5: unclosed '('");
+        assert_eq!(error("1 + 2)"), "line 1, column 6: unmatched ')'");
+        assert_eq!(error("1 2"), "line 1, column 3: expected an operator");
+        assert_eq!(error("1 +"), "line 1, column 4: expected a number or '('");
+        assert_eq!(error("* 2"), "line 1, column 1: expected a number or '('");
+        assert_eq!(error("()"), "line 1, column 2: expected a number or '('");
+        let e = parse("1 - 2", &[('+', 1)]).unwrap_err();
+        assert_eq!(e.to_string(), "line 1, column 3: unsupported operator '-'");
+    }
+
+    #[test]
+    fn test_overflow() {
+        let big = "3037000500 * 3037000500 + 1";
+        let e = parse(big, LEFT_TO_RIGHT).unwrap();
+        assert_eq!(
+            e.eval::<i64>().unwrap_err().to_string(),
+            "line 1, column 12: 3037000500 * 3037000500 overflows i64"
+        );
+        assert_eq!(e.eval::<i128>().unwrap(), 9223372037000250001);
+        let e = parse("-(9223372036854775807 + 1)", LEFT_TO_RIGHT).unwrap();
+        assert!(e.eval::<i64>().is_err());
+        assert_eq!(
+            e.eval::<BigInt>().unwrap().to_string(),
+            "-9223372036854775808"
+        );
+    }
+
+    #[test]
+    fn test_large_literals() {
+        // 2^63 doesn't fit in i64, but it's an ordinary literal to i128 and BigInt.
+        let e = parse("1 + 9223372036854775808 * 2", LEFT_TO_RIGHT).unwrap();
+        assert_eq!(
+            e.eval::<i64>().unwrap_err().to_string(),
+            "line 1, column 5: 9223372036854775808 overflows i64"
+        );
+        assert_eq!(e.eval::<i128>().unwrap(), 18446744073709551618);
+        let e = parse(
+            "99999999999999999999999999999999999999999 + 1",
+            LEFT_TO_RIGHT,
+        )
+        .unwrap();
+        assert!(e.eval::<i128>().is_err());
+        assert_eq!(
+            e.eval::<BigInt>().unwrap().to_string(),
+            "100000000000000000000000000000000000000000"
+        );
+    }
+}
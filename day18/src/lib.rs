@@ -0,0 +1,124 @@
+pub mod expr;
+pub mod numbers;
+
+use advent::{Error, Solution};
+use expr::{Precedence, Token, ADDITION_FIRST, LEFT_TO_RIGHT};
+use numbers::Number;
+
+// The puzzle's expressions have no operator precedence: everything is evaluated left to right.
+pub fn eval(line: &str) -> i64 {
+    return expr::parse(line, LEFT_TO_RIGHT).unwrap().eval().unwrap();
+}
+
+// In part 2, addition comes before multiplication.
+pub fn p2_eval(line: &str) -> i64 {
+    return expr::parse(line, ADDITION_FIRST).unwrap().eval().unwrap();
+}
+
+// Syntax errors don't depend on precedence, so parse() checks each line once, and the parts
+// only have to build their own tree from its tokens.
+fn check_expression(line: &str) -> Result<Vec<Token>, Error> {
+    let tokens = expr::tokenize(line)?;
+    expr::parse_tokens(&tokens, line.len() + 1, LEFT_TO_RIGHT)?;
+    return Ok(tokens);
+}
+
+// The sum of every line's value, or an error pointing at the operation that overflowed N, or at
+// an operator that `precedence` doesn't have.
+pub fn sum<N: Number>(lines: &[Vec<Token>], precedence: &Precedence) -> Result<N, Error> {
+    let mut total = N::from_i64(0);
+    for (i, tokens) in lines.iter().enumerate() {
+        let end = tokens.last().map_or(1, |token| token.column + 1);
+        let tree = expr::parse_tokens(tokens, end, precedence).map_err(|e| e.below(i))?;
+        let value: N = tree.eval().map_err(|e| e.below(i))?;
+        total = match total.add(&value) {
+            Some(total) => total,
+            None => {
+                let msg = format!("adding {} to the total overflows {}", value, N::NAME);
+                return Err(Error::at(i + 1, 1, msg));
+            }
+        };
+    }
+    return Ok(total);
+}
+
+// Both parts' answers, evaluated with N.  Puzzle is the same thing with i64, except that it has
+// no way to report overflow but to panic.
+pub fn solve_with<N: Number>(text: &str) -> Result<(N, N), Error> {
+    let lines = Puzzle::parse(text)?;
+    let part1 = sum(&lines, LEFT_TO_RIGHT)?;
+    let part2 = sum(&lines, ADDITION_FIRST)?;
+    return Ok((part1, part2));
+}
+
+fn sum_or_panic(lines: &[Vec<Token>], precedence: &Precedence) -> i64 {
+    match sum(lines, precedence) {
+        Ok(total) => total,
+        Err(e) => panic!("{} (try day18 --numbers big)", e),
+    }
+}
+
+#[test]
+fn test_eval() {
+    assert_eq!(eval("1 + 1"), 2);
+    assert_eq!(eval("2 * 3"), 6);
+    assert_eq!(eval("1 + 2 * 4"), 12);
+    assert_eq!(eval("1 + (2 * 4)"), 9);
+    assert_eq!(eval("1 + ((2 * 4))"), 9);
+    assert_eq!(eval("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))"), 12240);
+    assert_eq!(
+        eval("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2"),
+        13632
+    );
+}
+
+#[test]
+fn test_p2_eval() {
+    assert_eq!(p2_eval("1 + 1"), 2);
+    assert_eq!(p2_eval("2 * 3"), 6);
+    assert_eq!(p2_eval("1 + 2 * 4"), 12);
+    assert_eq!(p2_eval("3 * 2 + 4"), 18);
+    assert_eq!(p2_eval("(3 * 2) + 4"), 10);
+    assert_eq!(p2_eval("1 + (2 * 4)"), 9);
+    assert_eq!(p2_eval("1 + ((2 * 4))"), 9);
+    assert_eq!(p2_eval("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))"), 669060);
+    assert_eq!(
+        p2_eval("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2"),
+        23340
+    );
+}
+
+#[test]
+fn test_sum_errors() {
+    let lines = vec![
+        expr::tokenize("1 + 2").unwrap(),
+        expr::tokenize("3 - 4").unwrap(),
+    ];
+    let e = sum::<i64>(&lines, &[('+', 1), ('*', 1)]).unwrap_err();
+    assert_eq!(e.to_string(), "line 2, column 3: unsupported operator '-'");
+    let lines = vec![expr::tokenize("1 +").unwrap()];
+    let e = sum::<i64>(&lines, LEFT_TO_RIGHT).unwrap_err();
+    assert_eq!(e.to_string(), "line 1, column 4: expected a number or '('");
+    assert_eq!(sum::<i64>(&[], LEFT_TO_RIGHT).unwrap(), 0);
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u32 = 18;
+    type Input<'a> = Vec<Vec<Token>>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(text: &str) -> Result<Vec<Vec<Token>>, Error> {
+        advent::parse_lines(text, check_expression)
+    }
+
+    fn part1(lines: &Vec<Vec<Token>>) -> i64 {
+        sum_or_panic(lines, LEFT_TO_RIGHT)
+    }
+
+    fn part2(lines: &Vec<Vec<Token>>) -> i64 {
+        sum_or_panic(lines, ADDITION_FIRST)
+    }
+}
@@ -0,0 +1,88 @@
+// The kinds of number an expression can be evaluated with.  Every operation is checked, so an
+// answer is either right or an error saying which operation overflowed.
+
+use num_bigint::BigInt;
+use std::fmt;
+
+pub trait Number: Sized + fmt::Display {
+    // What to call the type in messages and on the command line.
+    const NAME: &'static str;
+
+    fn from_i64(n: i64) -> Self;
+    // Each of these is None if the result doesn't fit.
+    fn parse(text: &str) -> Option<Self>;
+    fn add(&self, rhs: &Self) -> Option<Self>;
+    fn sub(&self, rhs: &Self) -> Option<Self>;
+    fn mul(&self, rhs: &Self) -> Option<Self>;
+    fn neg(&self) -> Option<Self>;
+}
+
+impl Number for i64 {
+    const NAME: &'static str = "i64";
+
+    fn from_i64(n: i64) -> i64 {
+        n
+    }
+    fn parse(text: &str) -> Option<i64> {
+        text.parse().ok()
+    }
+    fn add(&self, rhs: &i64) -> Option<i64> {
+        self.checked_add(*rhs)
+    }
+    fn sub(&self, rhs: &i64) -> Option<i64> {
+        self.checked_sub(*rhs)
+    }
+    fn mul(&self, rhs: &i64) -> Option<i64> {
+        self.checked_mul(*rhs)
+    }
+    fn neg(&self) -> Option<i64> {
+        self.checked_neg()
+    }
+}
+
+impl Number for i128 {
+    const NAME: &'static str = "i128";
+
+    fn from_i64(n: i64) -> i128 {
+        n as i128
+    }
+    fn parse(text: &str) -> Option<i128> {
+        text.parse().ok()
+    }
+    fn add(&self, rhs: &i128) -> Option<i128> {
+        self.checked_add(*rhs)
+    }
+    fn sub(&self, rhs: &i128) -> Option<i128> {
+        self.checked_sub(*rhs)
+    }
+    fn mul(&self, rhs: &i128) -> Option<i128> {
+        self.checked_mul(*rhs)
+    }
+    fn neg(&self) -> Option<i128> {
+        self.checked_neg()
+    }
+}
+
+// Never overflows, but allocates.
+impl Number for BigInt {
+    const NAME: &'static str = "big";
+
+    fn from_i64(n: i64) -> BigInt {
+        BigInt::from(n)
+    }
+    fn parse(text: &str) -> Option<BigInt> {
+        text.parse().ok()
+    }
+    fn add(&self, rhs: &BigInt) -> Option<BigInt> {
+        Some(self + rhs)
+    }
+    fn sub(&self, rhs: &BigInt) -> Option<BigInt> {
+        Some(self - rhs)
+    }
+    fn mul(&self, rhs: &BigInt) -> Option<BigInt> {
+        Some(self * rhs)
+    }
+    fn neg(&self) -> Option<BigInt> {
+        Some(-self)
+    }
+}
@@ -0,0 +1,9 @@
+advent::examples! {
+    day18::Puzzle;
+    expr1: "1 + 2 * 3 + 4 * 5 + 6" => part1 = 71, part2 = 231;
+    expr2: "1 + (2 * 3) + (4 * (5 + 6))" => part1 = 51, part2 = 51;
+    expr3: "2 * 3 + (4 * 5)" => part1 = 26, part2 = 46;
+    expr4: "5 + (8 * 3 + 9 + 3 * 4 * 3)" => part1 = 437, part2 = 1445;
+    expr5: "5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))" => part1 = 12240, part2 = 669060;
+    expr6: "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2" => part1 = 13632, part2 = 23340;
+}
@@ -0,0 +1,475 @@
+// An Earley parser for the message grammars.  It handles any context-free rules, including
+// ones that loop (like part 2's "8: 42 | 42 8"), left recursion and empty alternatives, and takes
+// at worst cubic time in the length of the message, with no backtracking.
+//
+// The chart has a set of items for every position in the message.  An item is an alternative of
+// some rule, how much of it has matched so far (the dot), and the position where it started
+// matching (the origin).  Each position's items are processed in turn:
+//
+//   predict:  the dot is before a rule, so add that rule's alternatives, starting here
+//   scan:     the dot is before a character that's next in the message, so move past it
+//   complete: the dot is at the end, so move the dot past this rule in every item that was
+//             waiting for it at the origin
+//
+// The message matches if, at its end, there's a complete item for the start rule that began at
+// position 0.  parse() then works back through the chart to find which rule matched which part
+// of the message; if there's no match, the last position with any items is as far as a match
+// got.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Symbol {
+    Char(u8),
+    // An index into Grammar's rules.
+    Rule(usize),
+}
+
+#[derive(Clone, Debug)]
+pub struct Grammar {
+    // What to call each rule in trees and reports.
+    names: Vec<usize>,
+    // Each rule's alternatives, each a sequence of symbols.
+    rules: Vec<Vec<Vec<Symbol>>>,
+    start: usize,
+    // Rules that can match the empty string.
+    nullable: Vec<bool>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Item {
+    rule: usize,
+    alt: usize,
+    dot: usize,
+    origin: usize,
+}
+
+impl Item {
+    fn advance(self) -> Item {
+        Item {
+            dot: self.dot + 1,
+            ..self
+        }
+    }
+}
+
+// The items found at each position of a message.
+struct Chart {
+    items: Vec<Vec<Item>>,
+    seen: Vec<HashSet<Item>>,
+    // waiting[pos][r] holds the items at pos whose dot is before rule r, ready for when r
+    // completes.
+    waiting: Vec<HashMap<usize, Vec<Item>>>,
+}
+
+impl Chart {
+    fn new(len: usize) -> Chart {
+        Chart {
+            items: vec![Vec::new(); len + 1],
+            seen: vec![HashSet::new(); len + 1],
+            waiting: vec![HashMap::new(); len + 1],
+        }
+    }
+
+    fn add(&mut self, grammar: &Grammar, pos: usize, item: Item) {
+        if self.seen[pos].insert(item) {
+            self.items[pos].push(item);
+            if let Some(Symbol::Rule(r)) = grammar.next_symbol(&item) {
+                self.waiting[pos].entry(r).or_default().push(item);
+            }
+        }
+    }
+}
+
+impl Grammar {
+    pub fn new(names: Vec<usize>, rules: Vec<Vec<Vec<Symbol>>>, start: usize) -> Grammar {
+        // A rule is nullable if one of its alternatives is all nullable rules.  Keep looking
+        // until nothing changes, since that can depend on rules further down the list.
+        let mut nullable = vec![false; rules.len()];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (r, alts) in rules.iter().enumerate() {
+                if nullable[r] {
+                    continue;
+                }
+                let empty = alts.iter().any(|alt| {
+                    alt.iter()
+                        .all(|s| matches!(s, Symbol::Rule(x) if nullable[*x]))
+                });
+                if empty {
+                    nullable[r] = true;
+                    changed = true;
+                }
+            }
+        }
+        Grammar {
+            names,
+            rules,
+            start,
+            nullable,
+        }
+    }
+
+    fn next_symbol(&self, item: &Item) -> Option<Symbol> {
+        self.rules[item.rule][item.alt].get(item.dot).copied()
+    }
+
+    fn chart(&self, message: &[u8]) -> Chart {
+        let n = message.len();
+        let mut chart = Chart::new(n);
+        for alt in 0..self.rules[self.start].len() {
+            let item = Item {
+                rule: self.start,
+                alt,
+                dot: 0,
+                origin: 0,
+            };
+            chart.add(self, 0, item);
+        }
+        for pos in 0..=n {
+            // Items get added to chart.items[pos] while it's being processed, so index rather
+            // than iterate.
+            let mut k = 0;
+            while k < chart.items[pos].len() {
+                let item = chart.items[pos][k];
+                k += 1;
+                match self.next_symbol(&item) {
+                    Some(Symbol::Rule(r)) => {
+                        for alt in 0..self.rules[r].len() {
+                            let predicted = Item {
+                                rule: r,
+                                alt,
+                                dot: 0,
+                                origin: pos,
+                            };
+                            chart.add(self, pos, predicted);
+                        }
+                        // A rule that can match nothing might never be completed here, so step
+                        // over it now.
+                        if self.nullable[r] {
+                            chart.add(self, pos, item.advance());
+                        }
+                    }
+                    Some(Symbol::Char(c)) => {
+                        if message.get(pos) == Some(&c) {
+                            chart.add(self, pos + 1, item.advance());
+                        }
+                    }
+                    None => {
+                        let mut j = 0;
+                        while let Some(w) = chart.waiting[item.origin]
+                            .get(&item.rule)
+                            .and_then(|waiting| waiting.get(j))
+                            .copied()
+                        {
+                            j += 1;
+                            chart.add(self, pos, w.advance());
+                        }
+                    }
+                }
+            }
+        }
+        return chart;
+    }
+
+    // Whether rule `rule` matches message[start..end], according to the chart.
+    fn completed(&self, chart: &Chart, rule: usize, start: usize, end: usize) -> bool {
+        self.rules[rule].iter().enumerate().any(|(alt, symbols)| {
+            chart.seen[end].contains(&Item {
+                rule,
+                alt,
+                dot: symbols.len(),
+                origin: start,
+            })
+        })
+    }
+
+    // Whether the whole of `message` matches the start rule.
+    pub fn matches(&self, message: &[u8]) -> bool {
+        let chart = self.chart(message);
+        return self.completed(&chart, self.start, 0, message.len());
+    }
+
+    // How the whole of `message` matches the start rule.  If it's ambiguous, this is one of the
+    // ways it matches.
+    pub fn parse(&self, message: &[u8]) -> Result<Tree, Failure> {
+        let chart = self.chart(message);
+        let mut stack = Vec::new();
+        if let Some(tree) = self.build(&chart, self.start, 0, message.len(), &mut stack) {
+            return Ok(tree);
+        }
+
+        let pos = (0..=message.len())
+            .rev()
+            .find(|&pos| !chart.items[pos].is_empty())
+            .unwrap_or(0);
+        let mut expected: Vec<(usize, u8)> = chart.items[pos]
+            .iter()
+            .filter_map(|item| match self.next_symbol(item) {
+                Some(Symbol::Char(c)) => Some((self.names[item.rule], c)),
+                _ => None,
+            })
+            .collect();
+        expected.sort_unstable();
+        expected.dedup();
+        return Err(Failure {
+            pos,
+            found: message.get(pos).copied(),
+            expected,
+        });
+    }
+
+    // The tree for rule `rule` matching message[start..end], if it does.  `stack` holds the
+    // matches being built further up the tree, so that a rule that can match the same span as
+    // itself (through empty rules, say) doesn't go round forever.
+    fn build(
+        &self,
+        chart: &Chart,
+        rule: usize,
+        start: usize,
+        end: usize,
+        stack: &mut Vec<(usize, usize, usize)>,
+    ) -> Option<Tree> {
+        if stack.contains(&(rule, start, end)) {
+            return None;
+        }
+        stack.push((rule, start, end));
+        let mut tree = None;
+        for (alt, symbols) in self.rules[rule].iter().enumerate() {
+            let item = Item {
+                rule,
+                alt,
+                dot: symbols.len(),
+                origin: start,
+            };
+            if !chart.seen[end].contains(&item) {
+                continue;
+            }
+            if let Some(children) = self.children(chart, item, end, stack) {
+                tree = Some(Tree {
+                    rule: self.names[rule],
+                    start,
+                    end,
+                    children,
+                });
+                break;
+            }
+        }
+        stack.pop();
+        return tree;
+    }
+
+    // Trees for the symbols before the dot of `item`, which is in chart[end], working back from
+    // the last one.  Characters have no tree of their own.
+    fn children(
+        &self,
+        chart: &Chart,
+        item: Item,
+        end: usize,
+        stack: &mut Vec<(usize, usize, usize)>,
+    ) -> Option<Vec<Tree>> {
+        if item.dot == 0 {
+            return Some(Vec::new());
+        }
+        let prev = Item {
+            dot: item.dot - 1,
+            ..item
+        };
+        match self.rules[item.rule][item.alt][prev.dot] {
+            Symbol::Char(_) => self.children(chart, prev, end - 1, stack),
+            Symbol::Rule(r) => {
+                for mid in (item.origin..=end).rev() {
+                    if !chart.seen[mid].contains(&prev) || !self.completed(chart, r, mid, end) {
+                        continue;
+                    }
+                    if let Some(child) = self.build(chart, r, mid, end, stack) {
+                        if let Some(mut children) = self.children(chart, prev, mid, stack) {
+                            children.push(child);
+                            return Some(children);
+                        }
+                    }
+                }
+                None
+            }
+        }
+    }
+}
+
+// Which rule matched which span of the message: message[start..end] matched `rule`, and each
+// child matched one of the rules it refers to, in order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tree {
+    pub rule: usize,
+    pub start: usize,
+    pub end: usize,
+    pub children: Vec<Tree>,
+}
+
+impl Tree {
+    // One line per rule, indented under the rule it's part of, with the span and the text it
+    // matched.
+    pub fn to_text(&self, message: &[u8]) -> String {
+        let mut lines = Vec::new();
+        self.text_lines(message, 0, &mut lines);
+        return lines.join("\n");
+    }
+
+    fn text_lines(&self, message: &[u8], depth: usize, lines: &mut Vec<String>) {
+        lines.push(format!(
+            "{}{}: {}..{} {:?}",
+            "  ".repeat(depth),
+            self.rule,
+            self.start,
+            self.end,
+            String::from_utf8_lossy(&message[self.start..self.end])
+        ));
+        for child in &self.children {
+            child.text_lines(message, depth + 1, lines);
+        }
+    }
+
+    // A Graphviz digraph, one node per rule match, for `dot -Tsvg`.
+    pub fn to_dot(&self, message: &[u8]) -> String {
+        let mut lines = vec!["digraph derivation {".to_string()];
+        lines.push("  node [shape=box, fontname=monospace];".to_string());
+        self.dot_lines(message, &mut 0, &mut lines);
+        lines.push("}".to_string());
+        return lines.join("\n") + "\n";
+    }
+
+    // Adds this node and everything under it, returning this node's number.
+    fn dot_lines(&self, message: &[u8], count: &mut usize, lines: &mut Vec<String>) -> usize {
+        let node = *count;
+        *count += 1;
+        let text = String::from_utf8_lossy(&message[self.start..self.end]);
+        let label = format!("{}: {}..{}\\n{:?}", self.rule, self.start, self.end, text);
+        lines.push(format!(
+            "  n{} [label=\"{}\"];",
+            node,
+            label.replace('"', "\\\"")
+        ));
+        for child in &self.children {
+            let child = child.dot_lines(message, count, lines);
+            lines.push(format!("  n{} -> n{};", node, child));
+        }
+        return node;
+    }
+}
+
+// Why a message didn't match: no way of matching it gets past `pos`.  `found` is the byte there
+// (None at the end of the message), and `expected` is the rules that could have matched a
+// character there instead, and their characters.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Failure {
+    pub pos: usize,
+    pub found: Option<u8>,
+    pub expected: Vec<(usize, u8)>,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let found = match self.found {
+            Some(c) => format!("{:?}", c as char),
+            None => "the end of the message".to_string(),
+        };
+        let expected: Vec<String> = self
+            .expected
+            .iter()
+            .map(|(rule, c)| format!("rule {} ({:?})", rule, *c as char))
+            .collect();
+        let expected = match expected.is_empty() {
+            true => "the end of the message".to_string(),
+            false => expected.join(" or "),
+        };
+        write!(
+            f,
+            "no match past position {}: found {}, expected {}",
+            self.pos, found, expected
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Symbol::{Char, Rule};
+
+    #[test]
+    fn test_left_recursion_and_empty_rules() {
+        // 0: 0 "a" | 1     (one or more a's, left recursive, after an optional b)
+        // 1: "b" |         (b, or nothing)
+        let grammar = Grammar::new(
+            vec![0, 1],
+            vec![
+                vec![vec![Rule(0), Char(b'a')], vec![Rule(1), Char(b'a')]],
+                vec![vec![Char(b'b')], vec![]],
+            ],
+            0,
+        );
+        assert!(grammar.matches(b"a"));
+        assert!(grammar.matches(b"baaa"));
+        assert!(!grammar.matches(b""));
+        assert!(!grammar.matches(b"ab"));
+        assert!(!grammar.matches(b"bb"));
+    }
+
+    #[test]
+    fn test_ambiguous() {
+        // 0: 1 1
+        // 1: "a" | "a" "a"
+        let grammar = Grammar::new(
+            vec![0, 1],
+            vec![
+                vec![vec![Rule(1), Rule(1)]],
+                vec![vec![Char(b'a')], vec![Char(b'a'), Char(b'a')]],
+            ],
+            0,
+        );
+        assert!(!grammar.matches(b"a"));
+        assert!(grammar.matches(b"aa"));
+        assert!(grammar.matches(b"aaa"));
+        assert!(grammar.matches(b"aaaa"));
+        assert!(!grammar.matches(b"aaaaa"));
+    }
+
+    #[test]
+    fn test_parse() {
+        // 0: 1 2
+        // 1: "a" | "a" 1
+        // 2: "b" |
+        let grammar = Grammar::new(
+            vec![0, 10, 20],
+            vec![
+                vec![vec![Rule(1), Rule(2)]],
+                vec![vec![Char(b'a')], vec![Char(b'a'), Rule(1)]],
+                vec![vec![Char(b'b')], vec![]],
+            ],
+            0,
+        );
+        let tree = grammar.parse(b"aab").unwrap();
+        assert_eq!(
+            tree.to_text(b"aab"),
+            "0: 0..3 \"aab\"\n  10: 0..2 \"aa\"\n    10: 1..2 \"a\"\n  20: 2..3 \"b\""
+        );
+        let tree = grammar.parse(b"a").unwrap();
+        assert_eq!(tree.children[1].start, tree.children[1].end);
+        assert!(tree.to_dot(b"a").contains("  n0 -> n1;\n"));
+
+        let failure = grammar.parse(b"aaba").unwrap_err();
+        assert_eq!(failure.pos, 3);
+        assert_eq!(
+            failure.to_string(),
+            "no match past position 3: found 'a', expected the end of the message"
+        );
+        let failure = grammar.parse(b"ac").unwrap_err();
+        assert_eq!(failure.expected, [(10, b'a'), (20, b'b')]);
+        assert_eq!(
+            failure.to_string(),
+            "no match past position 1: found 'c', expected rule 10 ('a') or rule 20 ('b')"
+        );
+        let failure = grammar.parse(b"").unwrap_err();
+        assert_eq!(failure.found, None);
+    }
+}
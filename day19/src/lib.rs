@@ -1,187 +1,312 @@
+pub mod earley;
+
+use advent::{Error, Solution};
+use earley::{Failure, Grammar, Symbol, Tree};
+use regex::{Regex, RegexBuilder};
 use std::collections::HashMap;
+use std::fmt;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 struct Sequence {
     rules: Vec<usize>,
 }
 
 impl Sequence {
-    fn parse(text: &str) -> Sequence {
-        let text = text.trim();
-        Sequence {
-            rules: text
-                .split_whitespace()
-                .map(|x| x.parse::<usize>().unwrap())
-                .collect(),
-        }
+    // Parse a sequence of rule ids, which is a slice of `line`.
+    fn parse(line: &str, text: &str) -> Result<Sequence, Error> {
+        let rules: Result<Vec<usize>, Error> = text
+            .split_whitespace()
+            .map(|x| advent::parse_number(x, advent::column_of(line, x)))
+            .collect();
+        Ok(Sequence { rules: rules? })
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 enum Rule {
     Char(u8),
     Alternates(Vec<Sequence>), // 3 5 | 5 3
 }
 
 impl Rule {
-    fn parse(ruletext: &str) -> Rule {
+    // Parse the part of a rule after the colon, which is a slice of `line`.
+    fn parse(line: &str, ruletext: &str) -> Result<Rule, Error> {
         if ruletext.starts_with("\"") {
-            return Rule::Char(ruletext.bytes().nth(1).unwrap());
+            let bytes = ruletext.as_bytes();
+            if bytes.len() != 3 || bytes[2] != b'"' {
+                let column = advent::column_of(line, ruletext);
+                return Err(Error::parse(column, "expected a single quoted character"));
+            }
+            return Ok(Rule::Char(bytes[1]));
         } else {
             let mut alts: Vec<Sequence> = Vec::new();
             for seqs in ruletext.split(" | ") {
-                alts.push(Sequence::parse(seqs));
+                alts.push(Sequence::parse(line, seqs)?);
             }
             // alts.sort_by(|a, b| a.len().cmp(&b.len()));
-            return Rule::Alternates(alts);
+            return Ok(Rule::Alternates(alts));
         }
     }
-}
-
-// Chooser is used to exhaustively search decision space.   "state" is source
-// that provides up to 128 binary choices.  "count" keeps track of how many
-// decisions the Chooser is being asked to make on any given run, so that we
-// can abort early when the decision space is exhausted.
-struct Chooser {
-    state: u128,
-    count: u32,
-}
-
-impl Chooser {
-    fn new(i: u128) -> Chooser {
-        Chooser { state: i, count: 0 }
-    }
 
-    fn choose(&mut self) -> usize {
-        let choice = self.state & 0x1;
-        self.state >>= 1;
-        self.count += 1;
-        return choice as usize;
-    }
-
-    fn exhausted(&self, i: u128) -> bool {
-        return i >= (1 << self.count);
+    fn references(&self) -> Vec<usize> {
+        match self {
+            Rule::Char(_) => Vec::new(),
+            Rule::Alternates(alts) => alts.iter().flat_map(|s| s.rules.clone()).collect(),
+        }
     }
 }
 
-#[derive(Debug)]
+// Part 2 replaces these two rules with versions that loop.
+const LOOPING_RULES: &str = "8: 42 | 42 8\n11: 42 31 | 42 11 31";
+
+#[derive(Clone, Debug, Default)]
 pub struct RuleMap {
     m: HashMap<usize, Rule>,
-    data: Vec<u8>,
 }
 
 impl RuleMap {
     pub fn new() -> RuleMap {
-        RuleMap {
-            m: HashMap::new(),
-            data: Vec::new(),
-        }
+        RuleMap { m: HashMap::new() }
     }
 
-    pub fn parse(&mut self, text: &str) {
-        for line in text.lines() {
-            let line = line.trim();
-            let parts: Vec<&str> = line.split(": ").take(2).collect();
+    pub fn parse(&mut self, text: &str) -> Result<(), Error> {
+        let mut ids: Vec<(usize, usize)> = Vec::new(); // (line index, rule id)
+        for (i, line) in text.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            let parts: Vec<&str> = trimmed.splitn(2, ": ").collect();
             if let [id, ruletext] = &parts[..] {
-                let rule = Rule::parse(ruletext);
-                self.m.insert(id.parse::<usize>().unwrap(), rule);
+                let rule = Rule::parse(line, ruletext).map_err(|e| e.below(i))?;
+                let column = advent::column_of(line, id);
+                let id = advent::parse_number(id, column).map_err(|e| e.below(i))?;
+                self.m.insert(id, rule);
+                ids.push((i, id));
+            } else {
+                return Err(Error::at(
+                    i + 1,
+                    advent::column_of(line, trimmed),
+                    "expected \"<n>: <rule>\"",
+                ));
+            }
+        }
+        // Every rule that's referred to has to exist, or matching would fail part way through.
+        for (i, id) in ids {
+            let refs = self.m[&id].references();
+            if let Some(missing) = refs.iter().find(|r| !self.m.contains_key(r)) {
+                return Err(Error::at(
+                    i + 1,
+                    1,
+                    format!("rule {} is not defined", missing),
+                ));
             }
         }
+        if !self.m.contains_key(&0) {
+            return Err(Error::at(1, 1, "rule 0 is not defined"));
+        }
+        return Ok(());
+    }
+
+    // Turn the rules into a Grammar that can match messages.  Rule ids can have gaps, so they're
+    // renumbered in order.
+    pub fn compile(&self) -> Grammar {
+        let mut ids: Vec<usize> = self.m.keys().copied().collect();
+        ids.sort_unstable();
+        let index: HashMap<usize, usize> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+        let rules = ids
+            .iter()
+            .map(|id| match &self.m[id] {
+                Rule::Char(c) => vec![vec![Symbol::Char(*c)]],
+                Rule::Alternates(alts) => alts
+                    .iter()
+                    .map(|seq| seq.rules.iter().map(|r| Symbol::Rule(index[r])).collect())
+                    .collect(),
+            })
+            .collect();
+        let start = index[&0];
+        return Grammar::new(ids, rules, start);
     }
 
-    // returns the number of bytes consumed, or 0 if no match was possible.
-    fn try_sequence(&self, index: usize, s: &Sequence, chooser: &mut Chooser) -> usize {
-        let mut consumed = 0usize;
-        for id in &(s.rules) {
-            let got = self.try_rule(index + consumed, *id, chooser);
-            if got == 0 {
-                consumed = 0;
-                break;
+    // Whether some rule that rule 0 depends on refers back to itself, directly or through other
+    // rules.  Only rules that aren't can be turned into a regular expression.
+    pub fn is_recursive(&self) -> bool {
+        // Depth first, remembering which rules are on the current path (false) and which have
+        // been fully explored (true).
+        fn visit(m: &HashMap<usize, Rule>, id: usize, state: &mut HashMap<usize, bool>) -> bool {
+            match state.get(&id) {
+                Some(false) => return true,
+                Some(true) => return false,
+                None => (),
             }
-            consumed += got;
+            state.insert(id, false);
+            if m[&id].references().iter().any(|r| visit(m, *r, state)) {
+                return true;
+            }
+            state.insert(id, true);
+            return false;
         }
-        return consumed;
+        return visit(&self.m, 0, &mut HashMap::new());
     }
 
-    // returns the number of bytes consumed, or 0 if no match was possible.
-    fn try_rule(&self, index: usize, rule_id: usize, chooser: &mut Chooser) -> usize {
-        if index >= self.data.len() {
-            return 0;
+    // A regular expression matching exactly the messages that match rule 0, or None if the rules
+    // are recursive.
+    pub fn to_pattern(&self) -> Option<String> {
+        if self.is_recursive() {
+            return None;
         }
-        let rule = &(self.m[&rule_id]);
-        let consumed: usize = match rule {
-            Rule::Char(x) => {
-                if self.data[index] == *x {
-                    1
-                } else {
-                    0
-                }
-            }
+        return Some(format!("^{}$", self.pattern(0, &mut HashMap::new())));
+    }
+
+    // The pattern for one rule.  Rules are usually referred to many times, so `done` keeps the
+    // ones already built.
+    fn pattern(&self, id: usize, done: &mut HashMap<usize, String>) -> String {
+        if let Some(pattern) = done.get(&id) {
+            return pattern.clone();
+        }
+        let pattern = match &self.m[&id] {
+            Rule::Char(c) => regex::escape(&(*c as char).to_string()),
             Rule::Alternates(alts) => {
-                let mut got = 0; // no match
-                let gots: Vec<usize> = alts
+                let alts: Vec<String> = alts
                     .iter()
-                    .map(|a| self.try_sequence(index, a, chooser))
-                    .filter(|g| *g > 0)
+                    .map(|seq| seq.rules.iter().map(|r| self.pattern(*r, done)).collect())
                     .collect();
-                if gots.len() > 1 {
-                    let select = chooser.choose();
-                    got = gots[select as usize];
-                } else if gots.len() == 1 {
-                    got = gots[0];
+                match alts.len() {
+                    1 => alts[0].clone(),
+                    _ => format!("(?:{})", alts.join("|")),
                 }
-                got
             }
         };
-        return consumed;
-    }
-
-    pub fn try_match(&mut self, text: &str) -> bool {
-        let text = text.trim();
-        println!("Trying to match {:?}", text);
-        self.data = text.as_bytes().iter().map(|&x| x as u8).collect();
-        // A poor man's backtracker: every time the algorithm has to
-        // make a binary choice between two options that both match,
-        // it pulls a bit from chooser to select between them.  By
-        // iterating through all possible pairs of decisions, I can
-        // exhaustively search the decision space.
-        let mut i: u128 = 0;
-        loop {
-            let mut chooser: Chooser = Chooser::new(i);
-            let consumed = self.try_rule(0, 0, &mut chooser);
-            if consumed == self.data.len() {
-                return true;
-            }
-            i = i + 1;
-            // if i is larger that the set of possible choices the
-            // chooser was asked to make, terminate early.  My
-            // previous code would try 1024 different chooser seeds,
-            // this is much faster (typically exhausts decision space
-            // after 8 tries).
-            if chooser.exhausted(i) {
-                println!("Gave up after {} tries ({} choices)", i, chooser.count);
-                break;
+        done.insert(id, pattern.clone());
+        return pattern;
+    }
+
+    // The fastest way to check messages against these rules: a single regular expression if the
+    // rules allow it, otherwise the Earley parser.
+    pub fn matcher(&self) -> Matcher {
+        if let Some(pattern) = self.to_pattern() {
+            // The patterns for real inputs are large, but the default size limit is only there
+            // to guard against untrusted patterns.
+            if let Ok(regex) = RegexBuilder::new(&pattern).size_limit(1 << 30).build() {
+                return Matcher::Regex(regex);
             }
         }
-        return false;
+        return Matcher::Earley(self.compile());
+    }
+
+    // Whether all of `text` matches rule 0.  To check many messages, make a matcher() once instead.
+    pub fn try_match(&self, text: &str) -> bool {
+        return self.matcher().matches(text);
+    }
+
+    // How `text` matches rule 0, or how far it got if it doesn't.
+    pub fn explain(&self, text: &str) -> Result<Tree, Failure> {
+        return self.compile().parse(text.trim().as_bytes());
+    }
+}
+
+pub enum Matcher {
+    Regex(Regex),
+    Earley(Grammar),
+}
+
+impl Matcher {
+    pub fn matches(&self, message: &str) -> bool {
+        let message = message.trim();
+        match self {
+            Matcher::Regex(regex) => regex.is_match(message),
+            Matcher::Earley(grammar) => grammar.matches(message.as_bytes()),
+        }
     }
 }
 
-pub fn count_matches(ruletext: &str, datatext: &str, part2: bool) -> usize {
+pub fn count_matches(ruletext: &str, datatext: &str, part2: bool) -> Result<usize, Error> {
     let mut rulemap = RuleMap::new();
-    rulemap.parse(ruletext);
+    rulemap.parse(ruletext)?;
     if part2 {
-        // Override 2 rules to introduce loops:
-        rulemap.parse("8: 42 | 42 8\n11: 42 31 | 42 11 31");
+        rulemap = with_loops(&rulemap)?;
+    }
+    let messages: Vec<&str> = datatext.lines().collect();
+    return Ok(count_valid(&rulemap, &messages));
+}
+
+// Override rules 8 and 11 to introduce loops.  The new rules refer to 42 and 31, so the input has
+// to define those.
+fn with_loops(rulemap: &RuleMap) -> Result<RuleMap, Error> {
+    if !rulemap.m.contains_key(&42) || !rulemap.m.contains_key(&31) {
+        return Err(Error::at(1, 1, "part 2 needs rules 42 and 31"));
+    }
+    let mut rulemap = rulemap.clone();
+    rulemap.parse(LOOPING_RULES)?;
+    return Ok(rulemap);
+}
+
+fn count_valid(rulemap: &RuleMap, messages: &[&str]) -> usize {
+    let matcher = rulemap.matcher();
+    return messages.iter().filter(|line| matcher.matches(line)).count();
+}
+
+// The puzzle input: the rules, then a blank line, then the messages to check against them.
+pub struct Notes<'a> {
+    rules: RuleMap,
+    // The rules with the part 2 loops, or why the input can't have them.
+    looping: Result<RuleMap, Error>,
+    messages: Vec<&'a str>,
+}
+
+// Part 2's answer: the count, or why the input has no part 2.  The reason shows as "n/a (...)",
+// which can't be mistaken for a count.
+#[derive(Debug, PartialEq)]
+pub enum Part2 {
+    Count(usize),
+    Unavailable(String),
+}
+
+impl fmt::Display for Part2 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part2::Count(count) => write!(f, "{}", count),
+            Part2::Unavailable(reason) => write!(f, "n/a ({})", reason),
+        }
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u32 = 19;
+    type Input<'a> = Notes<'a>;
+    type Answer1 = usize;
+    type Answer2 = Part2;
+
+    fn parse(text: &str) -> Result<Notes<'_>, Error> {
+        let parts: Vec<&str> = text.split("\n\n").collect();
+        if parts.len() != 2 {
+            return Err(Error::at(
+                1,
+                1,
+                "expected rules, a blank line, then messages",
+            ));
+        }
+        let mut rules = RuleMap::new();
+        rules.parse(parts[0])?;
+        let looping = with_loops(&rules);
+        Ok(Notes {
+            rules,
+            looping,
+            messages: parts[1].lines().collect(),
+        })
+    }
+
+    fn part1(notes: &Notes) -> usize {
+        count_valid(&notes.rules, &notes.messages)
     }
 
-    let mut count = 0;
-    for line in datatext.lines() {
-        let valid = rulemap.try_match(line);
-        println!("{} for {:?}", valid, line);
-        if valid {
-            count += 1;
+    fn part2(notes: &Notes) -> Part2 {
+        match &notes.looping {
+            Ok(rules) => Part2::Count(count_valid(rules, &notes.messages)),
+            Err(Error::Parse { msg, .. }) => Part2::Unavailable(msg.clone()),
+            Err(e) => Part2::Unavailable(e.to_string()),
         }
     }
-    return count;
 }
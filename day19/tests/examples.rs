@@ -0,0 +1,5 @@
+advent::examples! {
+    day19::Puzzle;
+    example: include_str!("../example.txt") => part1 = 2;
+    testcase: include_str!("../testcase.txt") => part1 = 3, part2 = 12;
+}
@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
 
+    use advent::Solution;
     use day19::*;
     use std::fs;
 
@@ -8,32 +9,120 @@ mod tests {
     pub fn test() {
         let mut rulemap = RuleMap::new();
 
-        rulemap.parse(
-            r#"0: 1 2 3
+        rulemap
+            .parse(
+                r#"0: 1 2 3
        1: "a"
        2: 4 5 | 5 4
        3: "b"
        4: 1 1 3
        5: 3 1 1"#,
-        );
+            )
+            .unwrap();
 
         assert_eq!(rulemap.try_match("aaabbaab"), true);
         assert_eq!(rulemap.try_match("aaabbaaa"), false);
     }
 
+    #[test]
+    pub fn explain_test() {
+        let mut rulemap = RuleMap::new();
+        rulemap
+            .parse("0: 4 1 5\n1: 2 3 | 3 2\n2: 4 4 | 5 5\n3: 4 5 | 5 4\n4: \"a\"\n5: \"b\"")
+            .unwrap();
+
+        let tree = rulemap.explain("ababbb").unwrap();
+        assert_eq!((tree.rule, tree.start, tree.end), (0, 0, 6));
+        let spans: Vec<(usize, usize, usize)> = tree
+            .children
+            .iter()
+            .map(|t| (t.rule, t.start, t.end))
+            .collect();
+        assert_eq!(spans, [(4, 0, 1), (1, 1, 5), (5, 5, 6)]);
+        assert!(tree
+            .to_dot(b"ababbb")
+            .contains("n2 [label=\"1: 1..5\\n\\\"babb\\\"\"];"));
+
+        let failure = rulemap.explain("aaaabbb").unwrap_err();
+        assert_eq!(failure.pos, 6);
+        assert_eq!(failure.found, Some(b'b'));
+        assert_eq!(
+            failure.to_string(),
+            "no match past position 6: found 'b', expected the end of the message"
+        );
+        let failure = rulemap.explain("bababa").unwrap_err();
+        assert_eq!(
+            failure.to_string(),
+            "no match past position 0: found 'b', expected rule 4 ('a')"
+        );
+    }
+
+    #[test]
+    pub fn regex_test() {
+        let mut rulemap = RuleMap::new();
+        rulemap
+            .parse("0: 4 1 5\n1: 2 3 | 3 2\n2: 4 4 | 5 5\n3: 4 5 | 5 4\n4: \"a\"\n5: \"b\"")
+            .unwrap();
+        assert!(!rulemap.is_recursive());
+        assert_eq!(
+            rulemap.to_pattern().unwrap(),
+            "^a(?:(?:aa|bb)(?:ab|ba)|(?:ab|ba)(?:aa|bb))b$"
+        );
+        assert!(matches!(rulemap.matcher(), Matcher::Regex(_)));
+        assert!(rulemap.try_match("ababbb"));
+        assert!(!rulemap.try_match("aaaabbb"));
+
+        rulemap.parse("1: 2 3 | 3 1").unwrap();
+        assert!(rulemap.is_recursive());
+        assert_eq!(rulemap.to_pattern(), None);
+        assert!(matches!(rulemap.matcher(), Matcher::Earley(_)));
+
+        // Recursion in a rule that 0 doesn't use doesn't count.
+        rulemap.parse("1: 2 3 | 3 2\n6: 6 4").unwrap();
+        assert!(!rulemap.is_recursive());
+    }
+
+    #[test]
+    pub fn parse_error_test() {
+        let error = RuleMap::new().parse("0: 1\n  1 \"a\"").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: expected \"<n>: <rule>\""
+        );
+        let error = RuleMap::new().parse("0: 1 2\n1: \"a\"").unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 1: rule 2 is not defined");
+    }
+
     #[test]
     pub fn p2_test_no_override() {
         let contents = fs::read_to_string("testcase.txt").expect("bleh");
         let parts: Vec<&str> = contents.split("\n\n").collect();
-        let count = count_matches(parts[0], parts[1], false);
+        let count = count_matches(parts[0], parts[1], false).unwrap();
         assert_eq!(3, count);
     }
 
+    #[test]
+    pub fn p2_test_without_loop_rules() {
+        let rules = "0: 1 2\n1: \"a\"\n2: \"b\"";
+        assert_eq!(count_matches(rules, "ab\nba", false).unwrap(), 1);
+        let error = count_matches(rules, "ab\nba", true).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 1: part 2 needs rules 42 and 31"
+        );
+        let text = format!("{}\n\nab\nba", rules);
+        let notes = Puzzle::parse(&text).unwrap();
+        assert_eq!(
+            Puzzle::part2(&notes).to_string(),
+            "n/a (part 2 needs rules 42 and 31)"
+        );
+    }
+
     #[test]
     pub fn p2_test_with_override() {
         let contents = fs::read_to_string("testcase.txt").expect("bleh");
         let parts: Vec<&str> = contents.split("\n\n").collect();
-        let count = count_matches(parts[0], parts[1], true);
+        let count = count_matches(parts[0], parts[1], true).unwrap();
         assert_eq!(12, count);
     }
 
@@ -56,8 +145,8 @@ mod tests {
         let contents = fs::read_to_string("testcase.txt").expect("bleh");
         let parts: Vec<&str> = contents.split("\n\n").collect();
         let mut rulemap = RuleMap::new();
-        rulemap.parse(parts[0]);
-        rulemap.parse("8: 42 | 42 8\n11: 42 31 | 42 11 31");
+        rulemap.parse(parts[0]).unwrap();
+        rulemap.parse("8: 42 | 42 8\n11: 42 31 | 42 11 31").unwrap();
         assert_eq!(true, rulemap.try_match("bbabbbbaabaabba"));
         // this one:
         assert_eq!(true, rulemap.try_match("babbbbaabbbbbabbbbbbaabaaabaaa"));
@@ -89,7 +178,7 @@ mod tests {
     pub fn simple_loop_test() {
         let contents = fs::read_to_string("simple_loop.txt").expect("bleh");
         let mut rulemap = RuleMap::new();
-        rulemap.parse(&contents);
+        rulemap.parse(&contents).unwrap();
         dbg!(&rulemap);
         //                                  0123456789
         assert_eq!(true, rulemap.try_match("aaaaaabbbbbbab"));
@@ -0,0 +1,67 @@
+use advent::{Error, Solution};
+use regex::Regex;
+
+// A password database entry: "n-m c: password".
+pub struct Entry<'a> {
+    min: usize,
+    max: usize,
+    chr: char,
+    password: &'a str,
+}
+
+fn parse_entry<'a>(re_line: &Regex, line: &'a str) -> Result<Entry<'a>, Error> {
+    let ro = match re_line.captures(line) {
+        Some(ro) => ro,
+        None => return Err(Error::parse(1, "expected \"n-m c: password\"")),
+    };
+    let number = |i: usize| {
+        let m = ro.get(i).unwrap();
+        advent::parse_number::<usize>(m.as_str(), m.start() + 1)
+    };
+    let (min, max) = (number(1)?, number(2)?);
+    if min == 0 || max == 0 {
+        return Err(Error::parse(1, "positions count from 1"));
+    }
+    return Ok(Entry {
+        min,
+        max,
+        chr: ro.get(3).unwrap().as_str().chars().next().unwrap(),
+        password: ro.get(4).unwrap().as_str(),
+    });
+}
+
+// part 1 rules: "n-m c" indicates c must appear between n and m times, inclusive.
+fn p1_is_valid(e: &Entry) -> bool {
+    let chr_count = e.password.chars().filter(|c| *c == e.chr).count();
+    return (chr_count >= e.min) && (chr_count <= e.max);
+}
+
+// part 2 rules: "n-m c" indicates c must be present in either position n or m, but not both.
+// n and m are 1-indexed positions.
+fn p2_is_valid(e: &Entry) -> bool {
+    let c1 = e.password.chars().nth(e.min - 1).unwrap();
+    let c2 = e.password.chars().nth(e.max - 1).unwrap();
+    return (c1 == e.chr) ^ (c2 == e.chr);
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u32 = 2;
+    type Input<'a> = Vec<Entry<'a>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(text: &str) -> Result<Vec<Entry<'_>>, Error> {
+        let re_line: Regex = Regex::new(r"^\s*(\d+)-(\d+)\s+(\w):\s+(\w+)\s*$").unwrap();
+        advent::parse_lines(text, |line| parse_entry(&re_line, line))
+    }
+
+    fn part1(entries: &Vec<Entry>) -> usize {
+        entries.iter().filter(|e| p1_is_valid(e)).count()
+    }
+
+    fn part2(entries: &Vec<Entry>) -> usize {
+        entries.iter().filter(|e| p2_is_valid(e)).count()
+    }
+}
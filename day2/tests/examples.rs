@@ -0,0 +1,4 @@
+advent::examples! {
+    day2::Puzzle;
+    example: include_str!("../example.txt") => part1 = 2, part2 = 1;
+}
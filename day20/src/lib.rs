@@ -1,4 +1,5 @@
 #![allow(dead_code)]
+use advent::{Error, Solution};
 use num_integer::Roots;
 use std::cmp::max;
 use std::cmp::Ordering;
@@ -116,9 +117,30 @@ impl Tile {
         return Default::default();
     }
 
-    fn parse(&mut self, text: &str) {
+    // A tile is a "Tile <id>:" header, then 10 rows of 10 '#' or '.' pixels.
+    fn parse(&mut self, text: &str) -> Result<(), Error> {
         let lines: Vec<&str> = text.lines().map(|x| x.trim()).collect();
-        self.tile_id = lines[0][5..9].parse::<u16>().unwrap();
+        let id = lines[0]
+            .strip_prefix("Tile ")
+            .and_then(|x| x.strip_suffix(":"));
+        self.tile_id = match id {
+            Some(id) => advent::parse_number(id, 6)?,
+            None => return Err(Error::parse(1, "expected \"Tile <id>:\"")),
+        };
+        if lines.len() != 11 {
+            return Err(Error::parse(
+                1,
+                "expected 10 rows of pixels after the header",
+            ));
+        }
+        for (i, line) in lines.iter().enumerate().skip(1) {
+            if line.len() != 10 {
+                return Err(Error::at(i + 1, 1, "expected 10 pixels"));
+            }
+            if let Some(j) = line.find(|c| c != '#' && c != '.') {
+                return Err(Error::at(i + 1, j + 1, "expected '#' or '.'"));
+            }
+        }
         // Tiles are 10x10:
         self.edges[N] = (0..=9)
             .map(|x| lines[1].chars().nth(x).unwrap()) // surely there is a better way?
@@ -147,6 +169,7 @@ impl Tile {
         for index in 0..8 {
             self.bitmap[index] = line_to_bitmap(lines[2 + index]);
         }
+        return Ok(());
     }
 
     fn is_corner(&self) -> bool {
@@ -250,7 +273,7 @@ impl Tile {
     }
 
     fn dump(&self) {
-        println!(
+        eprintln!(
             "tile:{} connections:N:{:?},E:{:?},S:{:?},W:{:?} flipped:{} rotated:{}",
             self.tile_id,
             self.connections[0],
@@ -298,18 +321,24 @@ impl TileSet {
         return Default::default();
     }
 
-    pub fn parse(&mut self, text: &str) {
-        let mut count: u16 = 0;
-        for tile_text in text.split("\n\n") {
+    pub fn parse(&mut self, text: &str) -> Result<(), Error> {
+        let tiles = advent::parse_sections(text, |tile_text| {
             let mut tile = Tile::new();
-            tile.parse(tile_text);
+            tile.parse(tile_text)?;
+            Ok(tile)
+        })?;
+        let count = tiles.len() as u16;
+        for tile in tiles {
             self.tilemap.insert(tile.tile_id, tile);
-            count += 1;
         }
         self.n = count.sqrt() as u16;
-        assert_eq!(self.n * self.n, count); // fits square grid.
+        if self.n * self.n != count {
+            let msg = format!("{} tiles can't be laid out in a square grid", count);
+            return Err(Error::at(1, 1, msg));
+        }
         dbg!(count, self.n);
         self.grid = vec![0; count as usize];
+        return Ok(());
     }
 
     fn find_matching_edges(&self, edge_id: EdgeId) -> Vec<EdgeId> {
@@ -412,7 +441,7 @@ impl TileSet {
 
         let adj_tile_id = self.get_tile_id_from_coord(adj_x, adj_y);
         let adj_tile = self.tilemap[&adj_tile_id];
-        println!(
+        eprintln!(
             "PLACING from tile {},{} ({}) to tile {},{} via {}",
             adj_x,
             adj_y,
@@ -421,9 +450,9 @@ impl TileSet {
             y,
             dir_to_name(dir_from_adj)
         );
-        print!("  adj_tile: ");
+        eprint!("  adj_tile: ");
         adj_tile.dump();
-        println!(
+        eprintln!(
             "  adj_tile {} maps to {}: con={:?}",
             dir_to_name(dir_from_adj),
             dir_to_name(adj_tile.transform_dir(dir_from_adj)),
@@ -451,7 +480,7 @@ impl TileSet {
         while tile.transform_dir(dir_to_adj) != conn.dir {
             tile.rotate();
         }
-        print!("  placed: ");
+        eprint!("  placed: ");
         tile.dump();
     }
 
@@ -469,7 +498,7 @@ impl TileSet {
 
         // Layout all tiles relative to top-left.
         for y in 0..self.n as usize {
-            println!("");
+            eprintln!("");
             for x in 0..self.n as usize {
                 if (x == 0) && (y == 0) {
                     continue;
@@ -605,11 +634,11 @@ impl Image {
                     .iter()
                     .map(|x| pixel_value_to_char(*x) as char)
                     .collect();
-                print!(" {}", chrs);
+                eprint!(" {}", chrs);
             }
-            println!("");
+            eprintln!("");
             if (y % 8) == 7 {
-                println!("");
+                eprintln!("");
             }
         }
     }
@@ -642,28 +671,39 @@ impl Image {
     }
 }
 
-pub fn part1(text: &str) -> u64 {
-    let mut ts = TileSet::new();
-    ts.parse(text);
-    ts.connect();
-    let part1 = ts.get_corners().iter().map(|x| *x as u64).product();
-    return part1;
-}
+pub struct Puzzle;
 
-pub fn part2(text: &str) -> u64 {
-    let mut ts = TileSet::new();
-    ts.parse(text);
-    ts.connect();
-    ts.layout(0);
-    let img = ts.draw_image();
-    img.dump();
+impl Solution for Puzzle {
+    const DAY: u32 = 20;
+    type Input<'a> = TileSet;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-    let monsters = img.find_most_monsters(&SEAMONSTER);
-    dbg!(monsters);
+    // Parsing also connects the tiles' matching edges, which both parts need.
+    fn parse(text: &str) -> Result<TileSet, Error> {
+        let mut ts = TileSet::new();
+        ts.parse(text)?;
+        ts.connect();
+        Ok(ts)
+    }
+
+    fn part1(ts: &TileSet) -> u64 {
+        ts.get_corners().iter().map(|x| *x as u64).product()
+    }
 
-    let count_ones = img.pixels.iter().filter(|&x| *x == 1).count();
-    let count_monster_pixels = monsters * SEAMONSTER.len();
-    let count_noise_pixels = count_ones - count_monster_pixels;
+    fn part2(ts: &TileSet) -> u64 {
+        let mut ts = ts.clone();
+        ts.layout(0);
+        let img = ts.draw_image();
+        img.dump();
 
-    return count_noise_pixels as u64;
+        let monsters = img.find_most_monsters(&SEAMONSTER);
+        dbg!(monsters);
+
+        let count_ones = img.pixels.iter().filter(|&x| *x == 1).count();
+        let count_monster_pixels = monsters * SEAMONSTER.len();
+        let count_noise_pixels = count_ones - count_monster_pixels;
+
+        return count_noise_pixels as u64;
+    }
 }
@@ -0,0 +1,4 @@
+advent::examples! {
+    day20::Puzzle;
+    testcase: include_str!("../testcase.txt") => part1 = 20899048083289u64, part2 = 273;
+}
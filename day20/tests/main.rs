@@ -9,7 +9,7 @@ mod tests {
         let contents =
             fs::read_to_string("testcase.txt").expect("Something went wrong reading the file");
         let mut ts = day20::TileSet::new();
-        ts.parse(&contents);
+        ts.parse(&contents).unwrap();
         assert_eq!(ts.n, 3);
         ts.connect();
         ts.layout(1);
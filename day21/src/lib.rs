@@ -1,8 +1,8 @@
 #![allow(dead_code)]
 
+use advent::{Error, Solution};
 use std::collections::HashMap;
 use std::collections::HashSet;
-use std::fs;
 
 #[derive(Debug, Default)]
 struct Food<'a> {
@@ -15,17 +15,27 @@ impl<'a> Food<'a> {
         Default::default()
     }
 
-    fn parse(&mut self, line: &'a str) {
+    // "ingredient ingredient ... (contains allergen, allergen, ...)"
+    fn parse(&mut self, line: &'a str) -> Result<(), Error> {
         let parts = line.split(" (contains ").take(2).collect::<Vec<&str>>();
+        if parts.len() != 2 {
+            return Err(Error::parse(
+                1,
+                "expected ingredients then \"(contains ...)\"",
+            ));
+        }
         self.ingredients.extend(parts[0].split_whitespace());
-        let mut allergens: &str = parts[1];
-        allergens = &allergens[0..allergens.len() - 1];
+        let allergens: &str = match parts[1].strip_suffix(")") {
+            Some(allergens) => allergens,
+            None => return Err(Error::parse(line.len(), "expected ')'")),
+        };
         self.allergens.extend(allergens.split(", "));
+        return Ok(());
     }
 }
 
 #[derive(Default)]
-struct Foods<'a> {
+pub struct Foods<'a> {
     foods: Vec<Food<'a>>,
     all_allergens: HashSet<&'a str>,
     all_ingredients: HashSet<&'a str>,
@@ -39,15 +49,12 @@ impl<'a> Foods<'a> {
         Default::default()
     }
 
-    fn parse(&mut self, text: &'a str) {
-        self.foods = text
-            .lines()
-            .map(|line| {
-                let mut food = Food::new();
-                food.parse(line);
-                food
-            })
-            .collect();
+    fn parse(&mut self, text: &'a str) -> Result<(), Error> {
+        self.foods = advent::parse_lines(text, |line| {
+            let mut food = Food::new();
+            food.parse(line)?;
+            Ok(food)
+        })?;
         for food in self.foods.iter() {
             dbg!(food);
             self.all_allergens = self
@@ -73,6 +80,7 @@ impl<'a> Foods<'a> {
         }
         dbg!(&self.all_allergens);
         dbg!(&self.all_ingredients);
+        return Ok(());
     }
 
     // Returns true if a new mapping was added.
@@ -103,7 +111,7 @@ impl<'a> Foods<'a> {
                     .collect::<Vec<&'a str>>();
                 self.ingredients_map.insert(ingredients[0], allergen);
                 self.allergens_map.insert(allergen, ingredients[0]);
-                println!(
+                eprintln!(
                     "mapped ingredient {} to allergen {}",
                     ingredients[0], allergen
                 );
@@ -118,48 +126,60 @@ impl<'a> Foods<'a> {
     }
 }
 
-pub fn part1() -> usize {
-    let contents = fs::read_to_string("input.txt").expect("Something went wrong reading the file");
-    println!("Loaded {} bytes", contents.len());
+fn deduce(text: &str) -> Result<Foods<'_>, Error> {
     let mut foods = Foods::new();
-    foods.parse(&contents);
+    foods.parse(text)?;
     foods.deduce_all_ingredients();
-    let known_ingredients: HashSet<&str> = foods.ingredients_map.keys().map(|x| *x).collect();
-    let unknown_ingredients: HashSet<&str> = foods
-        .all_ingredients
-        .difference(&known_ingredients)
-        .map(|x| *x)
-        .collect();
-    dbg!(unknown_ingredients.len());
-    let result: usize = unknown_ingredients
-        .iter()
-        .map(|x| foods.ingredient_count[x])
-        .sum();
-    dbg!(result);
-
-    let mut sorted_allergens: Vec<&str> = foods.allergens_map.keys().map(|x| *x).collect();
-    sorted_allergens.sort();
-    for allergen in sorted_allergens.iter() {
-        print!("{},", foods.allergens_map[allergen]);
-    }
-    println!("");
-    /*
-    let part2: String =
-        sorted_allergens.iter()
-          .map(|x| foods.allergens_map[x].as_str())
-          .join(",");
-    */
-    dbg!(result);
-    return result;
+    return Ok(foods);
 }
 
 #[test]
 pub fn test_foods() {
     let contents =
-        fs::read_to_string("testcase.txt").expect("Something went wrong reading the file");
-    println!("Loaded {} bytes", contents.len());
+        std::fs::read_to_string("testcase.txt").expect("Something went wrong reading the file");
+    eprintln!("Loaded {} bytes", contents.len());
     let mut foods = Foods::new();
-    foods.parse(&contents);
+    foods.parse(&contents).unwrap();
     foods.deduce_all_ingredients();
     assert_eq!(foods.ingredients_map["fvjkl"], "soy");
 }
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u32 = 21;
+    type Input<'a> = Foods<'a>;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn parse(text: &str) -> Result<Foods<'_>, Error> {
+        deduce(text)
+    }
+
+    fn part1(foods: &Foods) -> usize {
+        let known_ingredients: HashSet<&str> = foods.ingredients_map.keys().map(|x| *x).collect();
+        let unknown_ingredients: HashSet<&str> = foods
+            .all_ingredients
+            .difference(&known_ingredients)
+            .map(|x| *x)
+            .collect();
+        dbg!(unknown_ingredients.len());
+        let result: usize = unknown_ingredients
+            .iter()
+            .map(|x| foods.ingredient_count[x])
+            .sum();
+        dbg!(result);
+        return result;
+    }
+
+    // The canonical dangerous ingredient list: ingredients sorted alphabetically by their allergen.
+    fn part2(foods: &Foods) -> String {
+        let mut sorted_allergens: Vec<&str> = foods.allergens_map.keys().map(|x| *x).collect();
+        sorted_allergens.sort();
+        let ingredients: Vec<&str> = sorted_allergens
+            .iter()
+            .map(|x| foods.allergens_map[x])
+            .collect();
+        return ingredients.join(",");
+    }
+}
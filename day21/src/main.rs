@@ -1,5 +1,3 @@
 fn main() {
-    println!("Hello, world!");
-    dbg!(day21::part1());
-    // dbg!(day21::part2());
+    advent::solve::<day21::Puzzle>();
 }
@@ -0,0 +1,4 @@
+advent::examples! {
+    day21::Puzzle;
+    testcase: include_str!("../testcase.txt") => part1 = 5, part2 = "mxmxvkd,sqjhc,fvjkl";
+}
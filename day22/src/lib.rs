@@ -1,9 +1,11 @@
 #![allow(dead_code)]
 
+use advent::{Error, Solution};
 use log::*;
 
 use std::collections::HashSet;
 use std::collections::VecDeque;
+use std::hash::BuildHasher;
 
 type Card = i32;
 type Deck = VecDeque<Card>;
@@ -20,7 +22,7 @@ fn deck_to_str(deck: &Deck) -> String {
 }
 
 #[derive(Clone, Default, Debug, Hash, Eq, PartialEq)]
-struct Game {
+pub struct Game {
     decks: [Deck; 2],
 }
 
@@ -29,25 +31,39 @@ impl Game {
         Default::default()
     }
 
-    fn parse_deck(&mut self, deck_index: usize, text: &str) {
-        self.decks[deck_index] = text
-            .lines()
+    // A deck is a "Player N:" header, then one card per line from the top of the deck down.
+    fn parse_deck(text: &str) -> Result<Deck, Error> {
+        match text.lines().next() {
+            Some(header) if header.starts_with("Player ") && header.ends_with(":") => (),
+            _ => return Err(Error::parse(1, "expected \"Player <n>:\"")),
+        }
+        text.lines()
+            .enumerate()
             .skip(1) // discard header.
-            .map(|x| x.parse::<i32>().unwrap())
-            .collect();
+            .map(|(i, x)| advent::parse_number::<Card>(x, 1).map_err(|e| e.below(i)))
+            .collect()
     }
 
-    fn parse(&mut self, text: &str) {
-        let parts: Vec<&str> = text.split("\n\n").collect();
-        self.parse_deck(0, parts[0]);
-        self.parse_deck(1, parts[1]);
+    fn parse(&mut self, text: &str) -> Result<(), Error> {
+        let decks = advent::parse_sections(text, Game::parse_deck)?;
+        if decks.len() != 2 {
+            return Err(Error::at(
+                1,
+                1,
+                format!("expected 2 decks, found {}", decks.len()),
+            ));
+        }
+        for (i, deck) in decks.into_iter().enumerate() {
+            self.decks[i] = deck;
+        }
+        return Ok(());
     }
 
     fn get_winner(&self) -> usize {
         return if self.decks[0].len() == 0 { 1 } else { 0 };
     }
 
-    fn do_round(&mut self, game_number: usize, mode: Mode) -> bool {
+    fn do_round<S: BuildHasher + Default>(&mut self, game_number: usize, mode: Mode) -> bool {
         debug!("Player 0 deck: {}", deck_to_str(&self.decks[0]));
         debug!("Player 1 deck: {}", deck_to_str(&self.decks[1]));
         if self.decks.iter().any(|deck| deck.len() == 0) {
@@ -72,7 +88,7 @@ impl Game {
                 }
                 debug!("Player 0 subdeck: {}", deck_to_str(&subgame.decks[0]));
                 debug!("Player 1 subdeck: {}", deck_to_str(&subgame.decks[1]));
-                subgame.do_game(game_number + 1, mode);
+                subgame.play::<S>(game_number + 1, mode);
                 let winner = subgame.get_winner();
                 if winner == 0 {
                     self.decks[0].push_back(cards[0]);
@@ -100,9 +116,14 @@ impl Game {
     }
 
     fn do_game(&mut self, game_number: usize, mode: Mode) {
+        self.play::<hashers::Std>(game_number, mode);
+    }
+
+    // Play a game to the end, remembering the states seen so far in a HashSet hashed by S.
+    fn play<S: BuildHasher + Default>(&mut self, game_number: usize, mode: Mode) {
         debug!("GAME {} started.", game_number);
-        let mut history: HashSet<Game> = HashSet::new();
-        while self.do_round(game_number, mode) {
+        let mut history: HashSet<Game, S> = HashSet::default();
+        while self.do_round::<S>(game_number, mode) {
             if history.contains(self) {
                 // player 1 (our 0) is the winner
                 self.decks[1].clear();
@@ -134,6 +155,34 @@ impl Game {
     }
 }
 
+// The puzzle, with each game's history hashed by S.  Puzzle hashes it with std's hasher.
+hashers::hashed!(Hashed, Puzzle);
+
+impl<S: BuildHasher + Default> Solution for Hashed<S> {
+    const DAY: u32 = 22;
+    type Input<'a> = Game;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(text: &str) -> Result<Game, Error> {
+        let mut game = Game::new();
+        game.parse(text)?;
+        Ok(game)
+    }
+
+    fn part1(game: &Game) -> i64 {
+        let mut game = game.clone();
+        game.play::<S>(1, Mode::Part1);
+        return game.score();
+    }
+
+    fn part2(game: &Game) -> i64 {
+        let mut game = game.clone();
+        game.play::<S>(1, Mode::Part2);
+        return game.score();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     extern crate simplelog;
@@ -144,12 +193,17 @@ mod tests {
 
     #[test]
     fn test_testcase() {
-        let _ = TermLogger::init(LevelFilter::Info, Config::default(), TerminalMode::Mixed);
+        let _ = TermLogger::init(
+            LevelFilter::Info,
+            Config::default(),
+            TerminalMode::Mixed,
+            ColorChoice::Auto,
+        );
         let contents =
             fs::read_to_string("testcase.txt").expect("Something went wrong reading the file");
         info!("Loaded {} bytes", contents.len());
         let mut game = Game::new();
-        game.parse(&contents);
+        game.parse(&contents).unwrap();
         game.do_game(1, Mode::Part1);
         debug!("game state = {:?}", &game);
         assert_eq!(game.decks[0].len(), 0);
@@ -159,12 +213,17 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let _ = TermLogger::init(LevelFilter::Info, Config::default(), TerminalMode::Mixed);
+        let _ = TermLogger::init(
+            LevelFilter::Info,
+            Config::default(),
+            TerminalMode::Mixed,
+            ColorChoice::Auto,
+        );
         let contents =
             fs::read_to_string("input.txt").expect("Something went wrong reading the file");
         info!("Loaded {} bytes", contents.len());
         let mut game = Game::new();
-        game.parse(&contents);
+        game.parse(&contents).unwrap();
         game.do_game(1, Mode::Part1);
         debug!("game state = {:?}", &game);
         info!("score={}", game.score());
@@ -172,12 +231,17 @@ mod tests {
 
     #[test]
     fn test_part2_testcase() {
-        let _ = TermLogger::init(LevelFilter::Info, Config::default(), TerminalMode::Mixed);
+        let _ = TermLogger::init(
+            LevelFilter::Info,
+            Config::default(),
+            TerminalMode::Mixed,
+            ColorChoice::Auto,
+        );
         let contents =
             fs::read_to_string("testcase.txt").expect("Something went wrong reading the file");
         info!("Loaded {} bytes", contents.len());
         let mut game = Game::new();
-        game.parse(&contents);
+        game.parse(&contents).unwrap();
         game.do_game(1, Mode::Part2);
         debug!("game state = {:?}", &game);
         info!("score={}", game.score());
@@ -186,12 +250,17 @@ mod tests {
 
     #[test]
     fn test_part2() {
-        let _ = TermLogger::init(LevelFilter::Info, Config::default(), TerminalMode::Mixed);
+        let _ = TermLogger::init(
+            LevelFilter::Info,
+            Config::default(),
+            TerminalMode::Mixed,
+            ColorChoice::Auto,
+        );
         let contents =
             fs::read_to_string("input.txt").expect("Something went wrong reading the file");
         info!("Loaded {} bytes", contents.len());
         let mut game = Game::new();
-        game.parse(&contents);
+        game.parse(&contents).unwrap();
         game.do_game(1, Mode::Part2);
         debug!("game state = {:?}", &game);
         info!("score={}", game.score());
@@ -0,0 +1,3 @@
+fn main() {
+    advent::solve::<day22::Puzzle>();
+}
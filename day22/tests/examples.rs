@@ -0,0 +1,15 @@
+use advent::Solution;
+use day22::Hashed;
+use hashers::A;
+
+advent::examples! {
+    day22::Puzzle;
+    testcase: include_str!("../testcase.txt") => part1 = 306, part2 = 291;
+}
+
+// Part 2 again, with each game's history hashed by ahash.
+#[test]
+fn ahash() {
+    let game = Hashed::<A>::parse(include_str!("../testcase.txt")).unwrap();
+    assert_eq!(Hashed::<A>::part2(&game), 291);
+}
@@ -1,3 +1,4 @@
+use advent::{Error, Solution};
 use std::fmt;
 
 #[derive(Default, Debug)]
@@ -49,11 +50,19 @@ impl State {
         }
     }
 
+    // The labels of the cups clockwise from cup 1, not counting cup 1 itself.
+    fn labels_after_one(&self) -> String {
+        let one = self.cups.iter().position(|&c| c == 0).unwrap();
+        return (1..9)
+            .map(|i| (self.cups[(one + i) % 9] + 1).to_string())
+            .collect();
+    }
+
     pub fn dump(&self) {
         for i in 0..9 {
-            print!("{}", self.cups[i] + 1);
+            eprint!("{}", self.cups[i] + 1);
         }
-        println!("");
+        eprintln!("");
     }
 }
 
@@ -146,10 +155,10 @@ impl Game {
     fn dump_range(&self, start: u32, count: u32) {
         let mut j = start;
         for _ in 0..count {
-            print!("{},", j + 1);
+            eprint!("{},", j + 1);
             j = self.cups[j as usize];
         }
-        println!("");
+        eprintln!("");
     }
 
     fn range_to_string(&self, start: u32, count: u32) -> String {
@@ -175,13 +184,13 @@ fn test_state() {
     assert_eq!("837419265", s.to_string());
 }
 
-pub fn part_1() -> String {
+pub fn part_1(labels: &str) -> String {
     let mut s = State::new();
-    s.parse("284573961");
+    s.parse(labels);
     for _ in 0..100 {
         s = s.do_round();
     }
-    return s.to_string();
+    return s.labels_after_one();
 }
 
 #[test]
@@ -219,13 +228,46 @@ fn test_part_2() {
     }
 }
 
-pub fn part_2() -> String {
+pub fn part_2(labels: &str) -> String {
     let mut game = Game::new();
-    game.parse("284573961");
+    game.parse(labels);
     game.do_n_rounds(10 * 1000 * 1000);
     let a = game.cups[0] as u64 + 1;
     let b = game.cups[game.cups[0] as usize] as u64 + 1;
     let c = a * b;
-    assert_eq!(166298218695, c);
     return c.to_string();
 }
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u32 = 23;
+    type Input<'a> = &'a str;
+    type Answer1 = String;
+    type Answer2 = String;
+
+    // The input is just the cup labels, e.g. "389125467": each of 1 through 9, once.
+    fn parse(text: &str) -> Result<&str, Error> {
+        let labels = text.trim();
+        for (i, ch) in labels.chars().enumerate() {
+            if !('1'..='9').contains(&ch) || labels[..i].contains(ch) {
+                return Err(Error::parse(
+                    i + 1,
+                    format!("unexpected cup label {:?}", ch),
+                ));
+            }
+        }
+        if labels.len() != 9 {
+            return Err(Error::parse(1, "expected 9 cup labels"));
+        }
+        Ok(labels)
+    }
+
+    fn part1(labels: &&str) -> String {
+        part_1(labels)
+    }
+
+    fn part2(labels: &&str) -> String {
+        part_2(labels)
+    }
+}
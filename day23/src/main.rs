@@ -1,6 +1,3 @@
-use day23::*;
-
 fn main() {
-    dbg!(part_1());
-    dbg!(part_2());
+    advent::solve::<day23::Puzzle>();
 }
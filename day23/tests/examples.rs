@@ -0,0 +1,4 @@
+advent::examples! {
+    day23::Puzzle;
+    example: "389125467" => part1 = 67384529, part2 = 149245887792u64;
+}
@@ -1,180 +1,44 @@
 #![allow(dead_code)]
 
+use advent::{Error, Solution};
+use automaton::{Automaton, Rules};
+use hexgrid::Hex;
 use std::collections::HashSet;
-use std::fs;
-use std::ops::Add;
-use std::ops::AddAssign;
-
-// Hexagonal coordinate system:
-// even rows have an implicit x+0.5 shift.
-//
-//     0,3  1,3  2,3 3,3
-//  0,2  1,2  2,2  3,2       N
-//     0,1  1,1  2,1  3,1  W-+-E
-//  0,0  1,0  2,0  3,0       S
-//
-// So, the movement delta from even and odd rows differ:
-//  from 1,1: NW=1,2 ( 0, 1)  NE=2,2 ( 1,1)  SW=1,0 (0, -1) SE=2,0 (1,-1)
-//  from 2,2: NW=1,3 (-1, 1)  NE=2,3 ( 0,1)  SW=1,1 (-1,-1) SE=2,1 (0,-1)
-
-// HexCoord: coordinate on a hexagonal layout.
-#[derive(Debug, Default, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
-struct HexCoord(isize, isize);
-
-const DELTA_W: HexCoord = HexCoord(-1, 0);
-const DELTA_E: HexCoord = HexCoord(1, 0);
-// DELTA for odd rows:
-const DELTA_ODD_NW: HexCoord = HexCoord(0, 1);
-const DELTA_ODD_NE: HexCoord = HexCoord(1, 1);
-const DELTA_ODD_SW: HexCoord = HexCoord(0, -1);
-const DELTA_ODD_SE: HexCoord = HexCoord(1, -1);
-// DELTA for even rows is DELTA_ODD minus 1.
-
-impl Add for HexCoord {
-    type Output = Self;
-
-    fn add(self, other: Self) -> Self {
-        Self(self.0 + other.0, self.1 + other.1)
-    }
-}
-
-impl AddAssign for HexCoord {
-    fn add_assign(&mut self, other: Self) {
-        *self = Self(self.0 + other.0, self.1 + other.1);
-    }
-}
-
-impl HexCoord {
-    fn offset(self) -> HexCoord {
-        // Returns -1 if current row is even, 0 otherwise.
-        if (self.1 & 0x1) == 0 {
-            HexCoord(-1, 0)
-        } else {
-            HexCoord(0, 0)
-        }
-    }
-
-    fn go_e(&self) -> HexCoord {
-        *self + DELTA_E
-    }
-    fn go_w(&self) -> HexCoord {
-        *self + DELTA_W
-    }
-    fn go_ne(&self) -> HexCoord {
-        *self + DELTA_ODD_NE + self.offset()
-    }
-    fn go_nw(&self) -> HexCoord {
-        *self + DELTA_ODD_NW + self.offset()
-    }
-    fn go_se(&self) -> HexCoord {
-        *self + DELTA_ODD_SE + self.offset()
-    }
-    fn go_sw(&self) -> HexCoord {
-        *self + DELTA_ODD_SW + self.offset()
-    }
-
-    fn get_neighbors(&self) -> [HexCoord; 6] {
-        [
-            self.go_ne(),
-            self.go_nw(),
-            self.go_se(),
-            self.go_sw(),
-            self.go_e(),
-            self.go_w(),
-        ]
-    }
-
-    fn from_path(path: &str) -> HexCoord {
-        let mut p = HexCoord(0, 0);
-        let mut it = path.chars();
-        loop {
-            let ch = it.next();
-            if ch.is_none() {
-                break;
-            }
-            let ch = ch.unwrap();
-            p = match ch {
-                'e' => p.go_e(),
-                'w' => p.go_w(),
-                'n' => {
-                    let c2 = it.next().unwrap();
-                    match c2 {
-                        'e' => p.go_ne(),
-                        'w' => p.go_nw(),
-                        _ => panic!("bad direction: n{}", c2),
-                    }
-                }
-                's' => {
-                    let c2 = it.next().unwrap();
-                    match c2 {
-                        'e' => p.go_se(),
-                        'w' => p.go_sw(),
-                        _ => panic!("bad direction: s{}", c2),
-                    }
-                }
-                _ => panic!("bad direction: {}", ch),
-            }
-        }
-        return p;
-    }
-}
+use std::hash::BuildHasher;
 
+// The black tiles, hashed by S.
 #[derive(Debug, Clone)]
-struct Tiles {
-    set: HashSet<HexCoord>,
+pub struct Tiles<S = hashers::Std> {
+    set: HashSet<Hex, S>,
 }
 
-impl Tiles {
-    fn new() -> Tiles {
+impl<S: BuildHasher + Default + Clone> Tiles<S> {
+    fn new() -> Tiles<S> {
         Tiles {
-            set: HashSet::new(),
+            set: HashSet::default(),
         }
     }
 
-    fn parse(&mut self, text: &str) {
-        for line in text.lines() {
-            let p = HexCoord::from_path(line);
+    fn parse(&mut self, text: &str) -> Result<(), Error> {
+        for p in advent::parse_lines(text, Hex::from_path)? {
             if self.set.contains(&p) {
                 self.set.remove(&p);
             } else {
                 self.set.insert(p);
             }
         }
+        return Ok(());
     }
 
-    // Count all adjacent black tiles.
-    fn count_neighbors(&self, p: HexCoord) -> usize {
-        return p
-            .get_neighbors()
-            .iter()
-            .filter(|n| self.set.contains(*n))
-            .count();
-    }
-
-    fn step(&self) -> Tiles {
-        let mut next = self.clone();
-
-        // first look at every black tile, and count the adjacent black tiles.
-        // If there are 0, or more than 2 black tiles, tile turns white.
-        for black_tile in self.set.iter() {
-            let (b_neighbors, w_neighbors): (Vec<HexCoord>, Vec<HexCoord>) = black_tile
-                .get_neighbors()
-                .iter()
-                .partition(|n| self.set.contains(*n));
-            if (b_neighbors.len() == 0) || (b_neighbors.len() > 2) {
-                next.set.remove(&black_tile);
-            }
-            // For every white tile next to a black tile, count the neighbors.
-            // If it's next to exactly 2 black tiles, turns black.
-            for white_tile in w_neighbors.iter() {
-                let count = self.count_neighbors(*white_tile);
-                if count == 2 {
-                    next.set.insert(*white_tile);
-                }
-            }
+    // The floor as an automaton.  Each day, a black tile with 0 or more than 2 black neighbours
+    // flips to white, and a white tile with exactly 2 flips to black: B2/S12.
+    fn floor(&self) -> Automaton<automaton::Hex, S> {
+        let rules = Rules::parse("B2/S12").unwrap();
+        let mut floor = Automaton::new(automaton::Hex, rules);
+        for tile in self.set.iter() {
+            floor.insert(*tile);
         }
-
-        return next;
+        return floor;
     }
 
     // Count all black tiles in the set.
@@ -184,43 +48,73 @@ impl Tiles {
 }
 
 #[test]
-fn test_hexcoord_from_path() {
-    assert_eq!(HexCoord(0, 0), HexCoord::from_path(""));
-    assert_eq!(HexCoord(0, 1), HexCoord::from_path("ne"));
-    assert_eq!(HexCoord(-1, 1), HexCoord::from_path("nw"));
-    assert_eq!(HexCoord(0, -1), HexCoord::from_path("se"));
-    assert_eq!(HexCoord(-1, -1), HexCoord::from_path("sw"));
-    assert_eq!(HexCoord(1, 0), HexCoord::from_path("e"));
-    assert_eq!(HexCoord(-1, 0), HexCoord::from_path("w"));
-    assert_eq!(HexCoord(1, 2), HexCoord::from_path("nene"));
-    assert_eq!(HexCoord(0, 2), HexCoord::from_path("nenw"));
+fn test_from_path() {
+    // The tiles used to be numbered in rows, with the even rows shifted half a tile west of
+    // the odd ones: hexgrid's OddR layout.
+    let tile = |path| Hex::from_path(path).unwrap().offset(hexgrid::Offset::OddR);
+    assert_eq!([0, 0], tile(""));
+    assert_eq!([0, 1], tile("ne"));
+    assert_eq!([-1, 1], tile("nw"));
+    assert_eq!([0, -1], tile("se"));
+    assert_eq!([-1, -1], tile("sw"));
+    assert_eq!([1, 0], tile("e"));
+    assert_eq!([-1, 0], tile("w"));
+    assert_eq!([1, 2], tile("nene"));
+    assert_eq!([0, 2], tile("nenw"));
 }
 
 #[test]
 fn test_part1() {
-    assert_eq!(254, part1());
+    let contents = std::fs::read_to_string("input.txt").unwrap();
+    let tiles = Puzzle::parse(&contents).unwrap();
+    assert_eq!(254, Puzzle::part1(&tiles));
 }
 
-pub fn part1() -> usize {
-    let contents = fs::read_to_string("input.txt").expect("Something went wrong reading the file");
-    println!("Loaded {} bytes", contents.len());
-    let mut tiles = Tiles::new();
-    tiles.parse(&contents);
-    return dbg!(tiles.count_all());
+#[test]
+fn test_part2() {
+    let contents = std::fs::read_to_string("input.txt").unwrap();
+    let tiles = Puzzle::parse(&contents).unwrap();
+    assert_eq!(3697, Puzzle::part2(&tiles));
 }
 
-pub fn part2() -> usize {
-    let contents = fs::read_to_string("input.txt").expect("Something went wrong reading the file");
-    println!("Loaded {} bytes", contents.len());
-    let mut tiles = Tiles::new();
-    tiles.parse(&contents);
-    for _ in 0..100 {
-        tiles = tiles.step();
+// The puzzle, with the black tiles hashed by S.  Puzzle hashes them with std's hasher.
+hashers::hashed!(Hashed, Puzzle);
+
+impl<S: BuildHasher + Default + Clone> Solution for Hashed<S> {
+    const DAY: u32 = 24;
+    type Input<'a> = Tiles<S>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(text: &str) -> Result<Tiles<S>, Error> {
+        let mut tiles = Tiles::new();
+        tiles.parse(text)?;
+        Ok(tiles)
+    }
+
+    fn part1(tiles: &Tiles<S>) -> usize {
+        return dbg!(tiles.count_all());
+    }
+
+    fn part2(tiles: &Tiles<S>) -> usize {
+        let mut floor = tiles.floor();
+        for _ in 0..100 {
+            floor.step();
+        }
+        return dbg!(floor.len());
     }
-    return dbg!(tiles.count_all());
 }
 
-#[test]
-fn test_part2() {
-    assert_eq!(3697, part2());
+// The black tiles at the start and after each of `days` days, for drawing.
+pub fn generations<S: BuildHasher + Default + Clone>(
+    tiles: &Tiles<S>,
+    days: usize,
+) -> Vec<Vec<Hex>> {
+    let mut floor = tiles.floor();
+    let mut generations = vec![floor.live().copied().collect()];
+    for _ in 0..days {
+        floor.step();
+        generations.push(floor.live().copied().collect());
+    }
+    return generations;
 }
@@ -1,6 +1,56 @@
-use day24::*;
+// day24 [--frames path] [--days N] [input.txt]
+//
+// With no options, solves both parts.  With --frames, draws the floor at the start and after each
+// of the days (100 by default) instead, as an animated GIF or a PNG or PPM file per day, according
+// to the path's extension.
+
+use advent::{Solution, Source};
+use std::env;
+use std::path::PathBuf;
+use std::process;
+
+fn fail(msg: String) -> ! {
+    eprintln!("day 24: {}", msg);
+    process::exit(1);
+}
 
 fn main() {
-    dbg!(part1());
-    dbg!(part2());
+    let mut frames = None;
+    let mut days = None;
+    let mut source = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--frames" => match args.next() {
+                Some(path) => frames = Some(PathBuf::from(path)),
+                None => fail("--frames needs a path, like floor.gif".to_string()),
+            },
+            "--days" => match args.next().map(|n| n.parse()) {
+                Some(Ok(n)) => days = Some(n),
+                _ => fail("--days needs a number".to_string()),
+            },
+            _ if source.is_none() => source = Some(Source::from_arg(&arg)),
+            _ => fail(format!("unexpected argument {:?}", arg)),
+        }
+    }
+    let path = match frames {
+        Some(path) => path,
+        None if days.is_none() => return advent::solve::<day24::Puzzle>(),
+        None => fail("--days needs --frames".to_string()),
+    };
+
+    let source = source.unwrap_or_else(|| Source::default_for(24, "input.txt"));
+    let text = match source.read() {
+        Ok(text) => text,
+        Err(e) => fail(e.to_string()),
+    };
+    let tiles = match day24::Puzzle::parse(&text) {
+        Ok(tiles) => tiles,
+        Err(e) => fail(e.report(&text)),
+    };
+    let generations = day24::generations(&tiles, days.unwrap_or(100));
+    match render::save(&path, &render::hexes(&generations, 4)) {
+        Ok(files) => eprintln!("wrote {}", render::describe(&files)),
+        Err(e) => fail(format!("{}: {}", path.display(), e)),
+    }
 }
@@ -0,0 +1,15 @@
+use advent::Solution;
+use day24::Hashed;
+use hashers::Xx;
+
+advent::examples! {
+    day24::Puzzle;
+    example: include_str!("../example.txt") => part1 = 10, part2 = 2208;
+}
+
+// Part 2 again, with the black tiles hashed by XxHash64.
+#[test]
+fn xxhash() {
+    let tiles = Hashed::<Xx>::parse(include_str!("../example.txt")).unwrap();
+    assert_eq!(Hashed::<Xx>::part2(&tiles), 2208);
+}
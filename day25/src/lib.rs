@@ -27,9 +27,10 @@
 // The door transforms the subject number of the card's public key according to the door's loop
 // size. The result is the same encryption key as the card calculated.
 
+use advent::{Error, Solution};
+
 const SUBJECT_NUMBER: usize = 7;
 const MODULO: usize = 20201227;
-const PUBLIC_KEYS: [usize; 2] = [14788856, 19316454];
 
 fn get_loop_size(public_key: usize) -> usize {
     let mut loop_size: usize = 0;
@@ -53,8 +54,31 @@ fn transform(subject_number: usize, loop_size: usize) -> usize {
     return value;
 }
 
-pub fn part1() -> usize {
-    let loopsize = dbg!(get_loop_size(PUBLIC_KEYS[0]));
-    let enc_key = transform(PUBLIC_KEYS[1], loopsize);
-    return enc_key;
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u32 = 25;
+    type Input<'a> = Vec<usize>;
+    type Answer1 = usize;
+    type Answer2 = &'static str;
+
+    // The two public keys, card first, one per line.
+    fn parse(text: &str) -> Result<Vec<usize>, Error> {
+        let public_keys = advent::parse_lines(text, |x| advent::parse_number(x, 1))?;
+        if public_keys.len() != 2 {
+            return Err(Error::at(1, 1, "expected two public keys"));
+        }
+        Ok(public_keys)
+    }
+
+    fn part1(public_keys: &Vec<usize>) -> usize {
+        let loopsize = dbg!(get_loop_size(public_keys[0]));
+        let enc_key = transform(public_keys[1], loopsize);
+        return enc_key;
+    }
+
+    // Day 25 only has one puzzle.
+    fn part2(_public_keys: &Vec<usize>) -> &'static str {
+        "n/a"
+    }
 }
@@ -1,5 +1,3 @@
-use day25::*;
-
 fn main() {
-    dbg!(part1());
+    advent::solve::<day25::Puzzle>();
 }
@@ -0,0 +1,4 @@
+advent::examples! {
+    day25::Puzzle;
+    example: "5764801\n17807724" => part1 = 14897079;
+}
@@ -0,0 +1,79 @@
+use advent::{Error, Solution};
+
+#[derive(Debug)]
+struct Coord {
+    x: usize,
+    y: usize,
+}
+
+pub type TreeMatrix = Vec<Vec<bool>>;
+
+// A row of the map: '#' is a tree, '.' is open ground.
+fn parse_row(line: &str) -> Result<Vec<bool>, Error> {
+    line.trim()
+        .chars()
+        .enumerate()
+        .map(|(i, ch)| match ch {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(Error::parse(i + 1, format!("unexpected {:?}", ch))),
+        })
+        .collect()
+}
+
+fn count_trees(data: &TreeMatrix, slope: &Coord) -> u64 {
+    let width = data[0].len();
+    let height = data.len();
+    let mut p = Coord { x: 0, y: 0 };
+    let mut count: u64 = 0;
+    while p.y < height {
+        // dbg!(&p, &data[p.y][p.x]);
+        if data[p.y][p.x] {
+            count += 1;
+        }
+        p.y += slope.y;
+        p.x = (p.x + slope.x) % width;
+    }
+    return count;
+}
+
+fn slope_product(data: &TreeMatrix) -> u64 {
+    /*
+    Right 1, down 1.
+    Right 3, down 1. (This is the slope you already checked.)
+    Right 5, down 1.
+    Right 7, down 1.
+    Right 1, down 2.
+    */
+    let product = dbg!(count_trees(data, &Coord { x: 1, y: 1 }))
+        * dbg!(count_trees(&data, &Coord { x: 3, y: 1 }))
+        * dbg!(count_trees(&data, &Coord { x: 5, y: 1 }))
+        * dbg!(count_trees(&data, &Coord { x: 7, y: 1 }))
+        * dbg!(count_trees(&data, &Coord { x: 1, y: 2 }));
+    return product;
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u32 = 3;
+    type Input<'a> = TreeMatrix;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(text: &str) -> Result<TreeMatrix, Error> {
+        let data = advent::parse_lines(text, parse_row)?;
+        if let Some(i) = data.iter().position(|row| row.len() != data[0].len()) {
+            return Err(Error::at(i + 1, 1, "rows must all be the same width"));
+        }
+        Ok(data)
+    }
+
+    fn part1(data: &TreeMatrix) -> u64 {
+        count_trees(data, &Coord { x: 3, y: 1 })
+    }
+
+    fn part2(data: &TreeMatrix) -> u64 {
+        slope_product(data)
+    }
+}
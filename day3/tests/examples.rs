@@ -0,0 +1,4 @@
+advent::examples! {
+    day3::Puzzle;
+    sample: include_str!("../sample.txt") => part1 = 7, part2 = 336;
+}
@@ -0,0 +1,158 @@
+#[macro_use]
+extern crate lazy_static;
+extern crate regex;
+
+use advent::{Error, Solution};
+use regex::Regex;
+use std::collections::HashMap;
+
+pub type Passport<'a> = HashMap<&'a str, &'a str>;
+
+// A passport is a run of "key:value" fields, separated by spaces or newlines.
+fn parse<'a>(text: &'a str) -> Result<Passport<'a>, Error> {
+    let mut p = Passport::new();
+    for (i, line) in text.lines().enumerate() {
+        for kv in line.split_whitespace() {
+            let parts: Vec<&str> = kv.split(":").collect();
+            if parts.len() != 2 {
+                let column = advent::column_of(line, kv);
+                return Err(Error::at(
+                    i + 1,
+                    column,
+                    format!("expected key:value, found {:?}", kv),
+                ));
+            }
+            p.insert(parts[0], parts[1]);
+        }
+    }
+    return Ok(p);
+}
+
+fn ppvalid(p: &Passport) -> bool {
+    /*
+    byr (Birth Year)
+    iyr (Issue Year)
+    eyr (Expiration Year)
+    hgt (Height)
+    hcl (Hair Color)
+    ecl (Eye Color)
+    pid (Passport ID)
+    cid (Country ID) -- optional
+    */
+    return p.contains_key("byr")
+        && p.contains_key("iyr")
+        && p.contains_key("eyr")
+        && p.contains_key("hgt")
+        && p.contains_key("hcl")
+        && p.contains_key("ecl")
+        && p.contains_key("pid");
+}
+
+// passport validator for part 2
+fn yearinrange(yr: &str, min: u32, max: u32) -> bool {
+    lazy_static! {
+        static ref re_year: Regex = Regex::new(r"^\d{4}$").unwrap();
+    }
+    if !re_year.is_match(yr) {
+        return false;
+    };
+    let value = yr.parse::<u32>().unwrap();
+    if (value < min) {
+        return false;
+    }
+    if (value > max) {
+        return false;
+    }
+    return true;
+}
+
+fn ppvalid2(p: &Passport) -> bool {
+    /*
+     * byr (Birth Year) - four digits; at least 1920 and at most 2002.
+     * iyr (Issue Year) - four digits; at least 2010 and at most 2020.
+     * eyr (Expiration Year) - four digits; at least 2020 and at most 2030.
+     * hgt (Height) - a number followed by either cm or in:
+     * If cm, the number must be at least 150 and at most 193.
+     * If in, the number must be at least 59 and at most 76.
+     * hcl (Hair Color) - a # followed by exactly six characters 0-9 or a-f.
+     * ecl (Eye Color) - exactly one of: amb blu brn gry grn hzl oth.
+     * pid (Passport ID) - a nine-digit number, including leading zeroes.
+     * cid (Country ID) - ignored, missing or not.
+     */
+    if !(p.contains_key("byr")
+        && p.contains_key("iyr")
+        && p.contains_key("eyr")
+        && p.contains_key("hgt")
+        && p.contains_key("hcl")
+        && p.contains_key("ecl")
+        && p.contains_key("pid"))
+    {
+        return false;
+    }
+    if !yearinrange(p["byr"], 1920, 2002) {
+        return false;
+    }
+    if !yearinrange(p["iyr"], 2010, 2020) {
+        return false;
+    }
+    if !yearinrange(p["eyr"], 2020, 2030) {
+        return false;
+    }
+
+    lazy_static! {
+        static ref re_hgt: Regex = Regex::new(r"^(\d+)(in|cm)$").unwrap();
+        static ref re_hcl: Regex = Regex::new(r"^#[0-9a-f]{6}$").unwrap();
+        static ref re_ecl: Regex = Regex::new(r"^(amb|blu|brn|gry|grn|hzl|oth)$").unwrap();
+        static ref re_pid: Regex = Regex::new(r"^\d{9}$").unwrap();
+    }
+    if !re_hcl.is_match(p["hcl"]) {
+        return false;
+    }
+    if !re_ecl.is_match(p["ecl"]) {
+        return false;
+    }
+    if !re_pid.is_match(p["pid"]) {
+        return false;
+    }
+
+    let parts = re_hgt.captures(p["hgt"]);
+    if parts.is_none() {
+        return false;
+    }
+    let parts = parts.unwrap();
+    let value = parts.get(1).unwrap().as_str().parse::<u32>().unwrap();
+    if parts.get(2).unwrap().as_str() == "in" {
+        if (value < 59) || (value > 76) {
+            return false;
+        }
+    } else {
+        if (value < 150) || (value > 193) {
+            return false;
+        }
+    }
+
+    return true;
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u32 = 4;
+    type Input<'a> = Vec<Passport<'a>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(text: &str) -> Result<Vec<Passport<'_>>, Error> {
+        let passports = advent::parse_sections(text, parse)?;
+        dbg!(passports.len());
+        Ok(passports)
+    }
+
+    fn part1(passports: &Vec<Passport>) -> usize {
+        passports.iter().filter(|x| ppvalid(&x)).count()
+    }
+
+    fn part2(passports: &Vec<Passport>) -> usize {
+        passports.iter().filter(|x| ppvalid2(&x)).count()
+    }
+}
@@ -0,0 +1,6 @@
+advent::examples! {
+    day4::Puzzle;
+    example: include_str!("../example.txt") => part1 = 2;
+    invalid: include_str!("../invalid.txt") => part2 = 0;
+    valid: include_str!("../valid.txt") => part2 = 4;
+}
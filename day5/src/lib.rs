@@ -0,0 +1,72 @@
+use advent::{Error, Solution};
+use std::convert::TryInto;
+
+// A boarding pass is 7 F/B characters for the row then 3 L/R characters for the column, which
+// read together as a binary seat id.
+fn str_to_seat_id(s: &str) -> Result<i32, Error> {
+    if s.len() != 10 {
+        return Err(Error::parse(1, "expected 10 characters"));
+    }
+    let mut value = 0i32;
+    for (i, ch) in s.chars().enumerate() {
+        let valid = if i < 7 { "FB" } else { "LR" };
+        if !valid.contains(ch) {
+            return Err(Error::parse(
+                i + 1,
+                format!("expected one of {}, found {:?}", valid, ch),
+            ));
+        }
+        value *= 2;
+        if (ch == 'B') || (ch == 'R') {
+            value += 1;
+        }
+    }
+    eprintln!("{} {}", s, value);
+    return Ok(value);
+}
+
+fn seat_id_to_row(id: i32) -> i32 {
+    return id >> 3;
+}
+
+fn find_my_seat(seat_ids: &[i32]) -> usize {
+    let min_row = seat_ids.iter().map(|&x| seat_id_to_row(x)).min().unwrap();
+    let max_row = seat_ids.iter().map(|&x| seat_id_to_row(x)).max().unwrap();
+    dbg!(min_row, max_row);
+    let min_seatid: usize = (min_row << 3).try_into().unwrap();
+    let max_seatid: usize = ((max_row << 3) + 7).try_into().unwrap();
+    let mut idset: Vec<bool> = Vec::new();
+    idset.resize(max_seatid + 1, false);
+    for &i in seat_ids {
+        let id: usize = i.try_into().unwrap();
+        idset[id] = true;
+    }
+    for id in (min_seatid + 8)..(max_seatid + 1 - 8) {
+        if !idset[id] {
+            eprintln!("my seat: {}", id);
+            return id;
+        }
+    }
+    panic!("no empty seat found");
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u32 = 5;
+    type Input<'a> = Vec<i32>;
+    type Answer1 = i32;
+    type Answer2 = usize;
+
+    fn parse(text: &str) -> Result<Vec<i32>, Error> {
+        advent::parse_lines(text, str_to_seat_id)
+    }
+
+    fn part1(seat_ids: &Vec<i32>) -> i32 {
+        *seat_ids.iter().max().unwrap()
+    }
+
+    fn part2(seat_ids: &Vec<i32>) -> usize {
+        find_my_seat(seat_ids)
+    }
+}
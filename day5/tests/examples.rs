@@ -0,0 +1,5 @@
+advent::examples! {
+    day5::Puzzle;
+    one_seat: "FBFBBFFRLR" => part1 = 357;
+    highest_seat: "BFFFBBFRRR\nFFFBBBFRRR\nBBFFBBFRLL" => part1 = 820;
+}
@@ -0,0 +1,73 @@
+use advent::{Error, Solution};
+
+use bitvector::BitVector;
+
+const SURVEY_SIZE: usize = 26;
+
+// A group's answers, as sets of the questions (0 for 'a' through 25 for 'z').
+pub struct SurveySet {
+    anyone: BitVector,   // questions anyone answered yes to.
+    everyone: BitVector, // questions everyone answered yes to.
+}
+
+impl SurveySet {
+    fn new() -> SurveySet {
+        SurveySet {
+            anyone: BitVector::new(SURVEY_SIZE),
+            everyone: BitVector::full(SURVEY_SIZE),
+        }
+    }
+
+    // Each line of a record is one person's answers: the letters of the questions they said yes to.
+    fn read_record(&mut self, record: &str) -> Result<(), Error> {
+        for (i, line) in record.lines().enumerate() {
+            let mut answers = BitVector::new(SURVEY_SIZE);
+            for (j, c) in line.chars().enumerate() {
+                if !c.is_ascii_lowercase() {
+                    return Err(Error::at(
+                        i + 1,
+                        j + 1,
+                        format!("expected a-z, found {:?}", c),
+                    ));
+                }
+                answers.set(c as usize - 'a' as usize, true);
+            }
+            self.anyone |= &answers;
+            self.everyone &= &answers;
+        }
+        return Ok(());
+    }
+
+    fn count_nonzero_answers(&self) -> u32 {
+        return self.anyone.count_all_ones() as u32;
+    }
+
+    fn count_allyes_answers(&self) -> u32 {
+        return self.everyone.count_all_ones() as u32;
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u32 = 6;
+    type Input<'a> = Vec<SurveySet>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(text: &str) -> Result<Vec<SurveySet>, Error> {
+        advent::parse_sections(text, |r| {
+            let mut ss: SurveySet = SurveySet::new();
+            ss.read_record(r)?;
+            Ok(ss)
+        })
+    }
+
+    fn part1(surveys: &Vec<SurveySet>) -> u32 {
+        surveys.iter().map(|ss| ss.count_nonzero_answers()).sum()
+    }
+
+    fn part2(surveys: &Vec<SurveySet>) -> u32 {
+        surveys.iter().map(|ss| ss.count_allyes_answers()).sum()
+    }
+}
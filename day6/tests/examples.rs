@@ -0,0 +1,4 @@
+advent::examples! {
+    day6::Puzzle;
+    example: include_str!("../example.txt") => part1 = 11, part2 = 6;
+}
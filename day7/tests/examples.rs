@@ -0,0 +1,5 @@
+advent::examples! {
+    day7::Puzzle;
+    example: include_str!("../example.txt") => part1 = 4, part2 = 32;
+    nested: include_str!("../example2.txt") => part2 = 126;
+}
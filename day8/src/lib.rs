@@ -0,0 +1,41 @@
+use advent::{Error, Solution};
+use handheld::{analysis, Computer, Halt};
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u32 = 8;
+    type Input<'a> = Computer;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(text: &str) -> Result<Computer, Error> {
+        Computer::parse(text)
+    }
+
+    // The value in the accumulator just before any instruction runs a second time.
+    fn part1(computer: &Computer) -> i64 {
+        let mut computer = computer.clone();
+        match computer.run() {
+            Halt::InfiniteLoop(_) => computer.acc,
+            halt => panic!("expected an infinite loop, but the program {}", halt),
+        }
+    }
+
+    // Find the one jmp/nop flip that lets the program terminate, then run the repaired program.
+    fn part2(computer: &Computer) -> i64 {
+        let repair = match analysis::repairs(&computer.program).first() {
+            Some(repair) => *repair,
+            None => panic!("no single jmp/nop flip lets the program complete"),
+        };
+        let mut repaired = computer.clone();
+        repaired.patch(repair.pc, repair.instruction);
+        match repaired.run() {
+            Halt::Terminated => repaired.acc,
+            halt => panic!(
+                "the repair at {} didn't work: the program {}",
+                repair.pc, halt
+            ),
+        }
+    }
+}
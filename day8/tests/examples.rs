@@ -0,0 +1,4 @@
+advent::examples! {
+    day8::Puzzle;
+    example: include_str!("../example.txt") => part1 = 5, part2 = 8;
+}
@@ -0,0 +1,98 @@
+use advent::{Error, Solution};
+use std::collections;
+
+#[derive(Debug)]
+struct Decoder {
+    window: collections::VecDeque<i64>,
+}
+
+impl Decoder {
+    fn new() -> Decoder {
+        Decoder {
+            window: collections::VecDeque::new(),
+        }
+    }
+
+    // returns true if the submitted number is valid
+    fn consume(&mut self, num: i64) -> bool {
+        let mut valid: bool = false;
+        if self.window.len() < 25 {
+            valid = true;
+        } else {
+            for i in 0..24 {
+                for j in i..25 {
+                    if self.window[i] + self.window[j] == num {
+                        valid = true;
+                        break;
+                    }
+                }
+                if valid {
+                    break;
+                }
+            }
+        }
+
+        self.window.push_back(num);
+        while self.window.len() > 25 {
+            self.window.pop_front();
+        }
+
+        return valid;
+    }
+}
+
+// Returns the first number that is not the sum of two of the 25 numbers before it.
+fn find_invalid(numbers: &[i64]) -> i64 {
+    let mut result: i64 = -1;
+    let mut decoder = Decoder::new();
+    for num in numbers.iter() {
+        if !decoder.consume(*num) {
+            result = *num;
+        }
+    }
+    return result;
+}
+
+// Returns the sum of the smallest and largest numbers in the contiguous range that adds up to
+// the invalid number.
+fn find_weakness(numbers: &[i64]) -> i64 {
+    let part1_result = find_invalid(numbers);
+
+    let mut i: usize = 0;
+    let mut j: usize = 1;
+    let mut sum = numbers[i] + numbers[j];
+    while sum != part1_result {
+        if sum < part1_result {
+            j += 1;
+            sum += numbers[j];
+        } else {
+            sum -= numbers[i];
+            i += 1;
+        }
+    }
+    dbg!(&i, &j);
+    let min = numbers[i..j + 1].iter().min().unwrap();
+    let max = numbers[i..j + 1].iter().max().unwrap();
+    return min + max;
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u32 = 9;
+    type Input<'a> = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(text: &str) -> Result<Vec<i64>, Error> {
+        advent::parse_lines(text, |x| advent::parse_number(x, 1))
+    }
+
+    fn part1(numbers: &Vec<i64>) -> i64 {
+        find_invalid(numbers)
+    }
+
+    fn part2(numbers: &Vec<i64>) -> i64 {
+        find_weakness(numbers)
+    }
+}
@@ -0,0 +1,4 @@
+advent::examples! {
+    day9::Puzzle;
+    example: include_str!("../example.txt") => part1 = 100;
+}
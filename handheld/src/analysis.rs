@@ -0,0 +1,195 @@
+// Static analysis of handheld programs, without running every variation of them.
+//
+// Every instruction has exactly one successor, so a program is a graph where each node has one
+// edge out: instruction i goes to i + 1, to i + N for "jmp N", or to the end for "hlt".  The
+// program terminates if the path from instruction 0 reaches the node just past the end.
+//
+// Flipping a single jmp to a nop (or a nop to a jmp) only changes what happens if the flipped
+// instruction is on that path.  The flip repairs a looping program exactly when the flipped
+// instruction's new successor is one of the instructions whose own path already reaches the
+// end: that path can't lead back through the flipped instruction, or the original program
+// would have terminated too.  So finding every repair takes one walk backwards from the end and
+// one walk forwards from the start.
+
+use crate::{Flow, Instruction, Operation};
+
+// Where an instruction at `pc` goes next in a program of `len` instructions, or None if that's
+// outside the program (other than just past its end).
+fn successor(pc: usize, instruction: &Instruction, len: usize) -> Option<usize> {
+    let next = match instruction.operation.opcode().flow {
+        Flow::Next => Some(pc as i64 + 1),
+        Flow::Jump => (pc as i64).checked_add(instruction.operand),
+        Flow::Halt => return Some(len),
+    };
+    match next {
+        Some(next) if next >= 0 && next <= len as i64 => Some(next as usize),
+        _ => None,
+    }
+}
+
+// The instruction you get by swapping jmp and nop, or None for anything else.
+pub fn flip(instruction: &Instruction) -> Option<Instruction> {
+    let operation = match instruction.operation {
+        Operation::Jmp => Operation::Nop,
+        Operation::Nop => Operation::Jmp,
+        _ => return None,
+    };
+    return Some(Instruction::new(operation, instruction.operand));
+}
+
+pub struct ControlFlow {
+    // successors[pc] is where instruction pc goes next.  There's one extra node, for the end.
+    successors: Vec<Option<usize>>,
+    // terminates[pc] is true if running from pc reaches the end.
+    terminates: Vec<bool>,
+}
+
+impl ControlFlow {
+    pub fn new(program: &[Instruction]) -> ControlFlow {
+        let len = program.len();
+        let mut successors: Vec<Option<usize>> = program
+            .iter()
+            .enumerate()
+            .map(|(pc, instruction)| successor(pc, instruction, len))
+            .collect();
+        successors.push(None);
+
+        let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); len + 1];
+        for (pc, next) in successors.iter().enumerate() {
+            if let Some(next) = next {
+                predecessors[*next].push(pc);
+            }
+        }
+        let mut terminates = vec![false; len + 1];
+        terminates[len] = true;
+        let mut pending = vec![len];
+        while let Some(pc) = pending.pop() {
+            for &p in &predecessors[pc] {
+                if !terminates[p] {
+                    terminates[p] = true;
+                    pending.push(p);
+                }
+            }
+        }
+        ControlFlow {
+            successors,
+            terminates,
+        }
+    }
+
+    // Where instruction `pc` goes next; program.len() is the end.
+    pub fn successor(&self, pc: usize) -> Option<usize> {
+        self.successors[pc]
+    }
+
+    // Whether running from `pc` reaches the end of the program.
+    pub fn terminates_from(&self, pc: usize) -> bool {
+        self.terminates[pc]
+    }
+
+    // The instructions that run, in order, when the program starts at 0, up to the point where
+    // it terminates, loops or jumps out of bounds.
+    pub fn path(&self) -> Vec<usize> {
+        let end = self.successors.len() - 1;
+        let mut seen = vec![false; end];
+        let mut path = Vec::new();
+        let mut pc = Some(0);
+        while let Some(p) = pc {
+            if p == end || seen[p] {
+                break;
+            }
+            seen[p] = true;
+            path.push(p);
+            pc = self.successors[p];
+        }
+        return path;
+    }
+}
+
+// A single instruction change that makes the program terminate.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Repair {
+    pub pc: usize,
+    pub instruction: Instruction,
+}
+
+// Every single jmp/nop flip that makes the program terminate, in the order the original program
+// reaches them.  A program that already terminates needs no repair, so gets none.
+pub fn repairs(program: &[Instruction]) -> Vec<Repair> {
+    let flow = ControlFlow::new(program);
+    if flow.terminates_from(0) {
+        return Vec::new();
+    }
+    let mut repairs = Vec::new();
+    for pc in flow.path() {
+        let instruction = match flip(&program[pc]) {
+            Some(instruction) => instruction,
+            None => continue,
+        };
+        match successor(pc, &instruction, program.len()) {
+            Some(next) if flow.terminates_from(next) => repairs.push(Repair { pc, instruction }),
+            _ => (),
+        }
+    }
+    return repairs;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_program, Computer, Halt};
+
+    const EXAMPLE: &str = "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6";
+
+    #[test]
+    fn test_control_flow() {
+        let program = parse_program(EXAMPLE).unwrap();
+        let flow = ControlFlow::new(&program);
+        assert_eq!(flow.path(), [0, 1, 2, 6, 7, 3, 4]);
+        assert_eq!(flow.successor(7), Some(3));
+        assert!(flow.terminates_from(8));
+        assert!(!flow.terminates_from(5));
+
+        let program = parse_program("nop +0\njmp -2").unwrap();
+        assert_eq!(ControlFlow::new(&program).successor(1), None);
+    }
+
+    // Check repairs() against flipping every instruction and running the result.
+    fn brute_force(program: &[Instruction]) -> Vec<Repair> {
+        let mut repairs = Vec::new();
+        for (pc, instruction) in program.iter().enumerate() {
+            if let Some(instruction) = flip(instruction) {
+                let mut computer = Computer::new(program.to_vec());
+                computer.patch(pc, instruction);
+                if computer.run() == Halt::Terminated {
+                    repairs.push(Repair { pc, instruction });
+                }
+            }
+        }
+        return repairs;
+    }
+
+    #[test]
+    fn test_repairs() {
+        let program = parse_program(EXAMPLE).unwrap();
+        let expected = Repair {
+            pc: 7,
+            instruction: Instruction::new(Operation::Nop, -4),
+        };
+        assert_eq!(repairs(&program), [expected]);
+
+        // Two ways out of the loop at 1: jump straight past it from 0, or don't loop at all.
+        let program = parse_program("nop +3\njmp +0\nacc +1\nacc +2").unwrap();
+        let found = repairs(&program);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found, brute_force(&program));
+
+        // Flipping 0 gets out of one loop, but only into another.
+        let program = parse_program("jmp +0\njmp +0\nacc +1").unwrap();
+        assert_eq!(repairs(&program), []);
+        assert_eq!(brute_force(&program), []);
+
+        // Nothing to fix.
+        assert_eq!(repairs(&parse_program("acc +1").unwrap()), []);
+    }
+}
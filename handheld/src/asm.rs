@@ -0,0 +1,230 @@
+// Assembly source for handheld programs.  It's the puzzle's format, one instruction per line,
+// plus a few things that make programs easier to write by hand:
+//
+//   ; a comment runs from a semicolon to the end of the line
+//   start:  acc +1      ; a label names the address of the instruction after it
+//           jmp start   ; jmp and nop operands can be labels instead of offsets
+//   end:                ; a label can sit on a line of its own
+//
+// disassemble() goes the other way, labelling every address that something jumps to.
+
+use crate::{Flow, Instruction, Opcode};
+use advent::{column_of, Error};
+use std::collections::HashMap;
+
+// Labels look like identifiers, so they can't be mistaken for numbers.
+fn is_label(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => (),
+        _ => return false,
+    }
+    return chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+}
+
+// Assemble the instruction in `code`, a slice of `line`.  `label` gives the operand that a label
+// stands for, if there's a label by that name.
+pub(crate) fn statement<F>(line: &str, code: &str, label: F) -> Result<Instruction, Error>
+where
+    F: Fn(&str) -> Option<i64>,
+{
+    let words: Vec<&str> = code.split_whitespace().collect();
+    let (name, operand) = match words.as_slice() {
+        [name] => (*name, None),
+        [name, operand] => (*name, Some(*operand)),
+        _ => {
+            let column = column_of(line, code);
+            return Err(Error::parse(column, "expected \"<operation> <operand>\""));
+        }
+    };
+    let opcode = match Opcode::named(name) {
+        Some(opcode) => opcode,
+        None => {
+            let msg = format!("unrecognized operation {:?}", name);
+            return Err(Error::parse(column_of(line, name), msg));
+        }
+    };
+    let operand = match (operand, opcode.operand) {
+        (None, false) => 0,
+        (None, true) => {
+            let msg = format!("{} needs an operand", name);
+            return Err(Error::parse(column_of(line, name), msg));
+        }
+        (Some(text), false) => {
+            let msg = format!("{} doesn't take an operand", name);
+            return Err(Error::parse(column_of(line, text), msg));
+        }
+        (Some(text), true) if opcode.relative && is_label(text) => match label(text) {
+            Some(operand) => operand,
+            None => {
+                let msg = format!("undefined label {:?}", text);
+                return Err(Error::parse(column_of(line, text), msg));
+            }
+        },
+        (Some(text), true) => advent::parse_number(text, column_of(line, text))?,
+    };
+    return Ok(Instruction::new(opcode.operation, operand));
+}
+
+// Assemble a whole program.  Errors point at the line and column they came from.
+pub fn assemble(text: &str) -> Result<Vec<Instruction>, Error> {
+    // First pass: find each label's address, and the lines that hold instructions.
+    let mut labels: HashMap<&str, usize> = HashMap::new();
+    let mut statements = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let mut code = match line.find(';') {
+            Some(end) => &line[..end],
+            None => line,
+        };
+        if let Some(colon) = code.find(':') {
+            let label = code[..colon].trim();
+            if !is_label(label) {
+                let msg = format!("{:?} isn't a valid label", label);
+                return Err(Error::at(i + 1, column_of(line, label), msg));
+            }
+            if labels.insert(label, statements.len()).is_some() {
+                let msg = format!("label {:?} is already defined", label);
+                return Err(Error::at(i + 1, column_of(line, label), msg));
+            }
+            code = &code[colon + 1..];
+        }
+        if !code.trim().is_empty() {
+            statements.push((i, line, code));
+        }
+    }
+
+    // Second pass: now that every label has an address, assemble the instructions.
+    let mut program = Vec::new();
+    for (pc, (i, line, code)) in statements.into_iter().enumerate() {
+        let label = |name: &str| labels.get(name).map(|&target| target as i64 - pc as i64);
+        program.push(statement(line, code, label).map_err(|e| e.below(i))?);
+    }
+    return Ok(program);
+}
+
+// Where a jmp or nop at `pc` points, if that's inside the program or just past its end.
+fn target(pc: usize, instruction: &Instruction, len: usize) -> Option<usize> {
+    if !instruction.operation.opcode().relative {
+        return None;
+    }
+    let target = pc as i64 + instruction.operand;
+    if target < 0 || target > len as i64 {
+        return None;
+    }
+    return Some(target as usize);
+}
+
+// Write `program` out as source, with a label at every address a jmp or nop points to, and each
+// instruction's address (and, for jumps, where it goes) in a comment.  Assembling the result
+// gives back the same program.
+pub fn disassemble(program: &[Instruction]) -> String {
+    let len = program.len();
+    let mut labelled = vec![false; len + 1];
+    for (pc, instruction) in program.iter().enumerate() {
+        if let Some(target) = target(pc, instruction, len) {
+            labelled[target] = true;
+        }
+    }
+
+    let mut lines = Vec::new();
+    for (pc, instruction) in program.iter().enumerate() {
+        let opcode = instruction.operation.opcode();
+        let label = match labelled[pc] {
+            true => format!("l{}:", pc),
+            false => String::new(),
+        };
+        let (code, comment) = match target(pc, instruction, len) {
+            Some(target) if opcode.flow == Flow::Jump => (
+                format!("{} l{}", opcode.name, target),
+                format!("{} -> {}", pc, target),
+            ),
+            Some(target) => (format!("{} l{}", opcode.name, target), pc.to_string()),
+            None => (instruction.to_string(), pc.to_string()),
+        };
+        lines.push(format!("{:<8}{:<12}; {}", label, code, comment));
+    }
+    if labelled[len] {
+        lines.push(format!("l{}:", len));
+    }
+    return lines.join("\n") + "\n";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_program, Computer, Halt, Operation};
+
+    #[test]
+    fn test_assemble() {
+        let source = "\
+            ; double acc forever, unless the nop is flipped
+                    acc +1
+            loop:   mul +2      ; acc *= 2
+                    nop done    ; flip this to stop early
+                    jmp loop
+            done:
+                    hlt";
+        let program = assemble(source).unwrap();
+        assert_eq!(program[1], Instruction::new(Operation::Mul, 2));
+        assert_eq!(program[2], Instruction::new(Operation::Nop, 2));
+        assert_eq!(program[3], Instruction::new(Operation::Jmp, -2));
+        assert_eq!(program[4], Instruction::new(Operation::Hlt, 0));
+        assert_eq!(program[4].to_string(), "hlt");
+
+        let mut computer = Computer::new(program);
+        assert_eq!(computer.run(), Halt::InfiniteLoop(1));
+        assert_eq!(computer.acc, 2);
+        computer.reset();
+        computer.patch(2, Instruction::new(Operation::Jmp, 2));
+        assert_eq!(computer.run(), Halt::Terminated);
+        assert_eq!(computer.acc, 2);
+    }
+
+    #[test]
+    fn test_assemble_errors() {
+        let error = |source: &str| assemble(source).unwrap_err().to_string();
+        assert_eq!(
+            error("a: nop +0\na: jmp a"),
+            "line 2, column 1: label \"a\" is already defined"
+        );
+        assert_eq!(
+            error("nop +0\n  jmp nowhere"),
+            "line 2, column 7: undefined label \"nowhere\""
+        );
+        assert_eq!(
+            error("acc start"),
+            "line 1, column 5: expected a number, found \"start\""
+        );
+        assert_eq!(
+            error("2go: nop +0"),
+            "line 1, column 1: \"2go\" isn't a valid label"
+        );
+        assert_eq!(
+            error("hlt +1"),
+            "line 1, column 5: hlt doesn't take an operand"
+        );
+        assert_eq!(error("\n\nacc"), "line 3, column 1: acc needs an operand");
+    }
+
+    #[test]
+    fn test_disassemble() {
+        let example = "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6";
+        let program = parse_program(example).unwrap();
+        let source = disassemble(&program);
+        assert_eq!(
+            source.lines().take(3).collect::<Vec<_>>(),
+            [
+                "l0:     nop l0      ; 0",
+                "l1:     acc +1      ; 1",
+                "        jmp l6      ; 2 -> 6",
+            ]
+        );
+        assert_eq!(assemble(&source).unwrap(), program);
+
+        let program = parse_program("jmp +2\njmp -5\nhlt").unwrap();
+        assert_eq!(
+            disassemble(&program),
+            "        jmp l2      ; 0 -> 2\n        jmp -5      ; 1\nl2:     hlt         ; 2\n"
+        );
+    }
+}
@@ -0,0 +1,71 @@
+// hhdb: step through a handheld program interactively.
+//
+//   hhdb [program.txt]
+//
+// Without a program, debugs day 8's input.  Type `help` at the prompt for the commands.
+
+use advent::Source;
+use handheld::debugger::Debugger;
+use handheld::Computer;
+use std::env;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use std::process;
+
+fn main() {
+    let source = match env::args().nth(1) {
+        Some(arg) => Source::from_arg(&arg),
+        None => Source::File(Path::new(env!("CARGO_MANIFEST_DIR")).join("../day8/input.txt")),
+    };
+    if source == Source::Stdin {
+        eprintln!("hhdb: commands come from stdin, so the program has to be a file");
+        process::exit(1);
+    }
+    let text = match source.read() {
+        Ok(text) => text,
+        Err(e) => {
+            eprintln!("hhdb: {}", e);
+            process::exit(1);
+        }
+    };
+    let computer = match Computer::parse(&text) {
+        Ok(computer) => computer,
+        Err(e) => {
+            eprintln!("hhdb: {}: {}", source.name(), e.report(&text));
+            process::exit(1);
+        }
+    };
+    println!(
+        "{}: {} instructions; type `help` for commands",
+        source.name(),
+        computer.program.len()
+    );
+
+    let mut debugger = Debugger::new(computer);
+    println!("{}", debugger.status());
+    let stdin = io::stdin();
+    let mut last = String::new();
+    loop {
+        print!("(hhdb) ");
+        io::stdout().flush().unwrap();
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).unwrap_or(0) == 0 {
+            println!();
+            break;
+        }
+        // An empty line repeats the last command, which makes stepping less tedious.
+        let line = match line.trim() {
+            "" => last.clone(),
+            line => line.to_string(),
+        };
+        if line == "q" || line == "quit" {
+            break;
+        }
+        match debugger.command(&line) {
+            Ok(output) if output.is_empty() => (),
+            Ok(output) => println!("{}", output),
+            Err(msg) => println!("error: {}", msg),
+        }
+        last = line;
+    }
+}
@@ -0,0 +1,226 @@
+// An interactive debugger for handheld programs.  Debugger::command() runs one command line and
+// returns what to show for it, so the hhdb binary is just a prompt loop around it.
+
+use crate::{analysis, Computer, Instruction};
+
+pub const HELP: &str = "\
+commands:
+  s, step [n]             run the next n instructions (default 1)
+  c, continue             run to the next breakpoint, or until the program halts
+  b, break [pc]           set a breakpoint, or list them
+  d, delete <pc>          clear a breakpoint
+  l, list [pc]            show the program around pc (default: the current pc)
+  p, print                show pc and acc
+  t, trace [on|off|n]     start or stop tracing, or show the last n traced steps
+  set <pc|acc> <n>        change a register
+  patch <pc> <op> <arg>   replace an instruction, e.g. `patch 7 nop -4`
+  repairs                 list the jmp/nop flips that would make the program terminate
+  r, reset                start the program over
+  h, help                 show this
+  q, quit                 leave the debugger";
+
+pub struct Debugger {
+    pub computer: Computer,
+}
+
+fn number<T: std::str::FromStr>(arg: Option<&&str>, what: &str) -> Result<T, String> {
+    match arg {
+        Some(text) => text
+            .parse()
+            .map_err(|_| format!("{} must be a number, got {:?}", what, text)),
+        None => Err(format!("missing {}", what)),
+    }
+}
+
+impl Debugger {
+    pub fn new(computer: Computer) -> Debugger {
+        Debugger { computer }
+    }
+
+    // Where the computer is: the next instruction, or why it can't run one.
+    pub fn status(&self) -> String {
+        let c = &self.computer;
+        match c.halted() {
+            Some(halt) => format!("halted: {} (acc {})", halt, c.acc),
+            None => format!(
+                "{:>5}: {:<10} acc {}",
+                c.pc, c.program[c.pc as usize], c.acc
+            ),
+        }
+    }
+
+    // A few instructions either side of `center`, marking the current one and breakpoints.
+    fn list(&self, center: i64) -> String {
+        let c = &self.computer;
+        let breakpoints: Vec<usize> = c.breakpoints().collect();
+        let start = (center - 5).max(0) as usize;
+        let end = ((center + 6).max(0) as usize).min(c.program.len());
+        let mut lines = Vec::new();
+        for pc in start..end {
+            let mark = if pc as i64 == c.pc { "=>" } else { "  " };
+            let stop = if breakpoints.contains(&pc) { "*" } else { " " };
+            lines.push(format!("{}{}{:>5}: {}", mark, stop, pc, c.program[pc]));
+        }
+        return lines.join("\n");
+    }
+
+    fn trace(&self, count: usize) -> String {
+        let trace = self.computer.trace();
+        let start = trace.len().saturating_sub(count);
+        let lines: Vec<String> = trace[start..]
+            .iter()
+            .map(|t| format!("{:>5}: {:<10} acc {}", t.pc, t.instruction, t.acc))
+            .collect();
+        if lines.is_empty() {
+            return "nothing traced (use `trace on` first)".to_string();
+        }
+        return lines.join("\n");
+    }
+
+    // Run one command line, returning what to print.  Bad commands are errors, and leave the
+    // computer as it was.
+    pub fn command(&mut self, line: &str) -> Result<String, String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let (name, args) = match words.split_first() {
+            Some((name, args)) => (*name, args),
+            None => return Ok(String::new()),
+        };
+        match name {
+            "s" | "step" => {
+                let count: usize = match args.first() {
+                    Some(_) => number(args.first(), "count")?,
+                    None => 1,
+                };
+                for _ in 0..count {
+                    if self.computer.step().is_err() {
+                        break;
+                    }
+                }
+                Ok(self.status())
+            }
+            "c" | "continue" => {
+                self.computer.run();
+                Ok(self.status())
+            }
+            "b" | "break" => {
+                if args.is_empty() {
+                    let pcs: Vec<String> =
+                        self.computer.breakpoints().map(|b| b.to_string()).collect();
+                    return Ok(format!("breakpoints: {}", pcs.join(" ")));
+                }
+                let pc: usize = number(args.first(), "pc")?;
+                if pc >= self.computer.program.len() {
+                    return Err(format!("there is no instruction {}", pc));
+                }
+                self.computer.set_breakpoint(pc);
+                Ok(format!("breakpoint at {}", pc))
+            }
+            "d" | "delete" => {
+                let pc: usize = number(args.first(), "pc")?;
+                match self.computer.clear_breakpoint(pc) {
+                    true => Ok(format!("deleted breakpoint at {}", pc)),
+                    false => Err(format!("there is no breakpoint at {}", pc)),
+                }
+            }
+            "l" | "list" => {
+                let center = match args.first() {
+                    Some(_) => number(args.first(), "pc")?,
+                    None => self.computer.pc,
+                };
+                Ok(self.list(center))
+            }
+            "p" | "print" => Ok(self.status()),
+            "t" | "trace" => match args.first() {
+                Some(&"on") => {
+                    self.computer.set_tracing(true);
+                    Ok("tracing on".to_string())
+                }
+                Some(&"off") => {
+                    self.computer.set_tracing(false);
+                    Ok("tracing off".to_string())
+                }
+                Some(_) => Ok(self.trace(number(args.first(), "count")?)),
+                None => Ok(self.trace(10)),
+            },
+            "set" => {
+                let value: i64 = number(args.get(1), "value")?;
+                match args.first() {
+                    Some(&"pc") => self.computer.pc = value,
+                    Some(&"acc") => self.computer.acc = value,
+                    _ => return Err("usage: set <pc|acc> <n>".to_string()),
+                }
+                Ok(self.status())
+            }
+            "patch" => {
+                let pc: usize = number(args.first(), "pc")?;
+                if pc >= self.computer.program.len() {
+                    return Err(format!("there is no instruction {}", pc));
+                }
+                let instruction =
+                    Instruction::parse(&args[1..].join(" ")).map_err(|e| e.to_string())?;
+                self.computer.patch(pc, instruction);
+                Ok(format!("{:>5}: {}", pc, instruction))
+            }
+            "repairs" => {
+                let repairs = analysis::repairs(&self.computer.program);
+                if repairs.is_empty() {
+                    return Ok("no single jmp/nop flip makes the program terminate".to_string());
+                }
+                let lines: Vec<String> = repairs
+                    .iter()
+                    .map(|r| {
+                        format!(
+                            "{:>5}: {} -> {}",
+                            r.pc, self.computer.program[r.pc], r.instruction
+                        )
+                    })
+                    .collect();
+                Ok(lines.join("\n"))
+            }
+            "r" | "reset" => {
+                self.computer.reset();
+                Ok(self.status())
+            }
+            "h" | "help" => Ok(HELP.to_string()),
+            _ => Err(format!("unknown command {:?}; try `help`", name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session() {
+        let program = "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6";
+        let mut db = Debugger::new(Computer::parse(program).unwrap());
+        assert_eq!(db.command("step 2").unwrap(), "    2: jmp +4     acc 1");
+        assert_eq!(db.command("b 7").unwrap(), "breakpoint at 7");
+        assert_eq!(db.command("c").unwrap(), "    7: jmp -4     acc 2");
+        assert_eq!(
+            db.command("c").unwrap(),
+            "halted: infinite loop at 1 (acc 5)"
+        );
+
+        assert_eq!(db.command("repairs").unwrap(), "    7: jmp -4 -> nop -4");
+        assert!(db.command("patch 7 mov +1").is_err());
+        assert_eq!(db.command("patch 7 nop -4").unwrap(), "    7: nop -4");
+        db.command("reset").unwrap();
+        db.command("trace on").unwrap();
+        assert_eq!(db.command("c").unwrap(), "    7: nop -4     acc 2");
+        assert_eq!(db.command("d 7").unwrap(), "deleted breakpoint at 7");
+        assert_eq!(db.command("c").unwrap(), "halted: terminated (acc 8)");
+        assert_eq!(db.command("trace 1").unwrap(), "    8: acc +6     acc 8");
+
+        assert!(db.command("frobnicate").is_err());
+        assert!(db.command("set pc").is_err());
+
+        db.command("reset").unwrap();
+        db.command("set acc 9223372036854775807").unwrap();
+        assert_eq!(
+            db.command("c").unwrap(),
+            "halted: overflow at 1 (acc 9223372036854775807)"
+        );
+    }
+}
@@ -0,0 +1,404 @@
+// The handheld game console's boot code: a tiny instruction set with an accumulator and a
+// program counter.
+//
+//   acc +N    add N to the accumulator
+//   jmp +N    jump N instructions, relative to this one
+//   nop +N    do nothing
+//
+// On top of the puzzle's three, OPCODES adds a couple of our own:
+//
+//   mul +N    multiply the accumulator by N
+//   hlt       stop, as though the program had run off its end
+//
+// A Computer runs a program one step at a time, and says why it stopped: the program ran off
+// its end, was about to run an instruction for the second time, jumped somewhere outside the
+// program, overflowed a register, or hit a breakpoint.
+
+pub mod analysis;
+pub mod asm;
+pub mod debugger;
+
+use advent::Error;
+use std::collections::BTreeSet;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Operation {
+    Acc,
+    Jmp,
+    Nop,
+    Mul,
+    Hlt,
+}
+
+// Where an instruction sends the program counter.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Flow {
+    // On to the next instruction.
+    Next,
+    // By the operand, relative to this instruction.
+    Jump,
+    // Just past the end of the program, which terminates it.
+    Halt,
+}
+
+// Everything the assembler, the Computer and the analysis need to know about an operation.  To
+// add an instruction, add an Operation and a row to OPCODES.
+pub struct Opcode {
+    pub operation: Operation,
+    pub name: &'static str,
+    pub flow: Flow,
+    // Whether the instruction takes an operand.  One that doesn't is written without one, and
+    // its operand is 0.
+    pub operand: bool,
+    // Whether the operand is an offset from this instruction, so the assembler accepts a label
+    // for it.  A nop's is, because flipping it gives a jmp.
+    pub relative: bool,
+    // The accumulator after the instruction runs, given the one before and the operand, or None
+    // if it overflows.
+    pub execute: fn(i64, i64) -> Option<i64>,
+}
+
+pub const OPCODES: &[Opcode] = &[
+    Opcode {
+        operation: Operation::Acc,
+        name: "acc",
+        flow: Flow::Next,
+        operand: true,
+        relative: false,
+        execute: |acc, n| acc.checked_add(n),
+    },
+    Opcode {
+        operation: Operation::Jmp,
+        name: "jmp",
+        flow: Flow::Jump,
+        operand: true,
+        relative: true,
+        execute: |acc, _| Some(acc),
+    },
+    Opcode {
+        operation: Operation::Nop,
+        name: "nop",
+        flow: Flow::Next,
+        operand: true,
+        relative: true,
+        execute: |acc, _| Some(acc),
+    },
+    Opcode {
+        operation: Operation::Mul,
+        name: "mul",
+        flow: Flow::Next,
+        operand: true,
+        relative: false,
+        execute: |acc, n| acc.checked_mul(n),
+    },
+    Opcode {
+        operation: Operation::Hlt,
+        name: "hlt",
+        flow: Flow::Halt,
+        operand: false,
+        relative: false,
+        execute: |acc, _| Some(acc),
+    },
+];
+
+impl Opcode {
+    pub fn named(name: &str) -> Option<&'static Opcode> {
+        OPCODES.iter().find(|opcode| opcode.name == name)
+    }
+}
+
+impl Operation {
+    pub fn opcode(self) -> &'static Opcode {
+        OPCODES
+            .iter()
+            .find(|opcode| opcode.operation == self)
+            .unwrap()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Instruction {
+    pub operation: Operation,
+    pub operand: i64,
+}
+
+impl Instruction {
+    pub fn new(operation: Operation, operand: i64) -> Instruction {
+        Instruction { operation, operand }
+    }
+
+    // Parse one line of a program, such as "jmp -3".  Labels mean nothing on their own, so
+    // operands have to be numbers.
+    pub fn parse(line: &str) -> Result<Instruction, Error> {
+        asm::statement(line, line, |_| None)
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let opcode = self.operation.opcode();
+        if !opcode.operand {
+            return f.pad(opcode.name);
+        }
+        f.pad(&format!("{} {:+}", opcode.name, self.operand))
+    }
+}
+
+// Parse a whole program.  This is the puzzle's one instruction per line, but also anything else
+// the assembler accepts.
+pub fn parse_program(text: &str) -> Result<Vec<Instruction>, Error> {
+    asm::assemble(text)
+}
+
+// Why a Computer stopped running.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Halt {
+    // The program counter reached the instruction just past the end of the program.
+    Terminated,
+    // The instruction at this pc has already run once, so the program would loop forever.
+    InfiniteLoop(usize),
+    // A jump left the program somewhere other than just past its end.
+    OutOfBounds(i64),
+    // The instruction at this pc would overflow the accumulator or the program counter.
+    Overflow(usize),
+    // There's a breakpoint on the next instruction, at this pc.
+    Breakpoint(usize),
+}
+
+impl fmt::Display for Halt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Halt::Terminated => write!(f, "terminated"),
+            Halt::InfiniteLoop(pc) => write!(f, "infinite loop at {}", pc),
+            Halt::OutOfBounds(pc) => write!(f, "pc {} is out of bounds", pc),
+            Halt::Overflow(pc) => write!(f, "overflow at {}", pc),
+            Halt::Breakpoint(pc) => write!(f, "breakpoint at {}", pc),
+        }
+    }
+}
+
+// One executed instruction, and the accumulator after it ran.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TraceEntry {
+    pub pc: usize,
+    pub instruction: Instruction,
+    pub acc: i64,
+}
+
+#[derive(Clone, Debug)]
+pub struct Computer {
+    pub program: Vec<Instruction>,
+    pub acc: i64,
+    pub pc: i64,
+    visited: Vec<bool>,
+    breakpoints: BTreeSet<usize>,
+    trace: Option<Vec<TraceEntry>>,
+}
+
+impl Computer {
+    pub fn new(program: Vec<Instruction>) -> Computer {
+        Computer {
+            visited: vec![false; program.len()],
+            program,
+            acc: 0,
+            pc: 0,
+            breakpoints: BTreeSet::new(),
+            trace: None,
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Computer, Error> {
+        return Ok(Computer::new(parse_program(text)?));
+    }
+
+    // Start over from the first instruction, keeping the program, breakpoints and tracing.
+    pub fn reset(&mut self) {
+        self.acc = 0;
+        self.pc = 0;
+        self.visited = vec![false; self.program.len()];
+        if let Some(trace) = &mut self.trace {
+            trace.clear();
+        }
+    }
+
+    // Replace the instruction at `pc`, e.g. to try out a repair.
+    pub fn patch(&mut self, pc: usize, instruction: Instruction) {
+        self.program[pc] = instruction;
+    }
+
+    // Why the computer can't run its next instruction, if it can't.  Breakpoints don't count.
+    pub fn halted(&self) -> Option<Halt> {
+        let len = self.program.len() as i64;
+        if self.pc == len {
+            return Some(Halt::Terminated);
+        }
+        if self.pc < 0 || self.pc > len {
+            return Some(Halt::OutOfBounds(self.pc));
+        }
+        if self.visited[self.pc as usize] {
+            return Some(Halt::InfiniteLoop(self.pc as usize));
+        }
+        if self.execute().is_none() {
+            return Some(Halt::Overflow(self.pc as usize));
+        }
+        return None;
+    }
+
+    // The accumulator and program counter after the instruction at pc runs, or None if either
+    // overflows.
+    fn execute(&self) -> Option<(i64, i64)> {
+        let instruction = self.program[self.pc as usize];
+        let opcode = instruction.operation.opcode();
+        let acc = (opcode.execute)(self.acc, instruction.operand)?;
+        let next = match opcode.flow {
+            Flow::Next => self.pc.checked_add(1)?,
+            Flow::Jump => self.pc.checked_add(instruction.operand)?,
+            Flow::Halt => self.program.len() as i64,
+        };
+        return Some((acc, next));
+    }
+
+    // Run the next instruction, unless the computer has halted.  An instruction that would
+    // overflow doesn't run at all, so the registers still hold what it overflowed.
+    pub fn step(&mut self) -> Result<(), Halt> {
+        if let Some(halt) = self.halted() {
+            return Err(halt);
+        }
+        let pc = self.pc as usize;
+        let instruction = self.program[pc];
+        let (acc, next) = match self.execute() {
+            Some(state) => state,
+            None => return Err(Halt::Overflow(pc)),
+        };
+        self.visited[pc] = true;
+        self.acc = acc;
+        self.pc = next;
+        if let Some(trace) = &mut self.trace {
+            trace.push(TraceEntry {
+                pc,
+                instruction,
+                acc: self.acc,
+            });
+        }
+        return Ok(());
+    }
+
+    // Run until the computer halts or reaches a breakpoint.  A breakpoint on the instruction
+    // it starts at doesn't count, so that running again continues past it.
+    pub fn run(&mut self) -> Halt {
+        if let Err(halt) = self.step() {
+            return halt;
+        }
+        loop {
+            let at_breakpoint = self.pc >= 0 && self.breakpoints.contains(&(self.pc as usize));
+            if at_breakpoint && self.halted().is_none() {
+                return Halt::Breakpoint(self.pc as usize);
+            }
+            if let Err(halt) = self.step() {
+                return halt;
+            }
+        }
+    }
+
+    pub fn set_breakpoint(&mut self, pc: usize) {
+        self.breakpoints.insert(pc);
+    }
+
+    // Returns false if there wasn't a breakpoint at `pc`.
+    pub fn clear_breakpoint(&mut self, pc: usize) -> bool {
+        self.breakpoints.remove(&pc)
+    }
+
+    pub fn breakpoints(&self) -> impl Iterator<Item = usize> + '_ {
+        self.breakpoints.iter().copied()
+    }
+
+    // Start or stop recording every instruction that runs.
+    pub fn set_tracing(&mut self, on: bool) {
+        self.trace = if on {
+            Some(self.trace.take().unwrap_or_default())
+        } else {
+            None
+        };
+    }
+
+    // Everything that has run since tracing started (or the last reset).
+    pub fn trace(&self) -> &[TraceEntry] {
+        match &self.trace {
+            Some(trace) => trace,
+            None => &[],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6";
+
+    #[test]
+    fn test_parse() {
+        let program = parse_program("nop +0\nacc -99").unwrap();
+        assert_eq!(program[1], Instruction::new(Operation::Acc, -99));
+        assert_eq!(program[1].to_string(), "acc -99");
+        let e = parse_program("nop +0\nmov +2").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 2, column 1: unrecognized operation \"mov\""
+        );
+    }
+
+    #[test]
+    fn test_halt() {
+        let mut computer = Computer::parse(EXAMPLE).unwrap();
+        assert_eq!(computer.run(), Halt::InfiniteLoop(1));
+        assert_eq!(computer.acc, 5);
+
+        computer.reset();
+        computer.patch(7, Instruction::new(Operation::Nop, -4));
+        assert_eq!(computer.run(), Halt::Terminated);
+        assert_eq!(computer.acc, 8);
+
+        let mut computer = Computer::parse("nop +0\njmp -2").unwrap();
+        assert_eq!(computer.run(), Halt::OutOfBounds(-1));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let mut computer = Computer::parse("acc +1\nmul +2\nhlt").unwrap();
+        computer.acc = i64::MAX - 1;
+        assert_eq!(computer.run(), Halt::Overflow(1));
+        assert_eq!(computer.acc, i64::MAX);
+        assert_eq!(computer.pc, 1);
+        assert_eq!(computer.step(), Err(Halt::Overflow(1)));
+        assert_eq!(Halt::Overflow(1).to_string(), "overflow at 1");
+
+        let mut computer = Computer::parse("acc -1\nnop +0").unwrap();
+        computer.acc = i64::MIN;
+        assert_eq!(computer.run(), Halt::Overflow(0));
+
+        let mut computer = Computer::parse("nop +0\njmp +9223372036854775807").unwrap();
+        assert_eq!(computer.run(), Halt::Overflow(1));
+    }
+
+    #[test]
+    fn test_breakpoints_and_trace() {
+        let mut computer = Computer::parse(EXAMPLE).unwrap();
+        computer.set_tracing(true);
+        computer.set_breakpoint(3);
+        assert_eq!(computer.run(), Halt::Breakpoint(3));
+        assert_eq!(computer.acc, 2);
+        assert_eq!(computer.run(), Halt::InfiniteLoop(1));
+
+        let pcs: Vec<usize> = computer.trace().iter().map(|t| t.pc).collect();
+        assert_eq!(pcs, [0, 1, 2, 6, 7, 3, 4]);
+        assert_eq!(computer.trace()[6].acc, 5);
+
+        computer.reset();
+        assert_eq!(computer.trace().len(), 0);
+        assert!(computer.clear_breakpoint(3));
+        assert_eq!(computer.run(), Halt::InfiniteLoop(1));
+    }
+}
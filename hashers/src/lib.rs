@@ -0,0 +1,156 @@
+// Day 17 started out comparing hash functions for its HashSet with a cargo feature per hasher,
+// which meant one rebuild per hasher and only one of them at a time.  Instead, the days with big
+// hash tables are generic over their BuildHasher, and these are the ones to pick from:
+//
+//   Std  std's default, SipHash 1-3 with random keys
+//   Xx   twox-hash's XxHash64
+//   Fx   fxhash, the one rustc uses
+//   A    ahash
+//
+// Kind names them at run time, and with_hasher! turns a Kind back into a type:
+//
+//   let count = hashers::with_hasher!(kind, |S| tiles::<S>(&text));
+//
+// hashed! declares a day's Hashed<S>, its Solution built with S, so `advent bench --hashers` can
+// time each of them.  With a second name, it also declares that as the Solution built with Std:
+//
+//   hashers::hashed!(Hashed, Puzzle);
+//
+//   impl<S: BuildHasher + Default> Solution for Hashed<S> { ... }
+
+use std::fmt;
+use std::hash::BuildHasherDefault;
+use std::str::FromStr;
+
+pub type Std = std::collections::hash_map::RandomState;
+pub type Xx = BuildHasherDefault<twox_hash::XxHash64>;
+pub type Fx = fxhash::FxBuildHasher;
+pub type A = ahash::RandomState;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Std,
+    Xx,
+    Fx,
+    A,
+}
+
+impl Kind {
+    pub const ALL: [Kind; 4] = [Kind::Std, Kind::Xx, Kind::Fx, Kind::A];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Kind::Std => "std",
+            Kind::Xx => "xx",
+            Kind::Fx => "fx",
+            Kind::A => "a",
+        }
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl FromStr for Kind {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Kind, String> {
+        match Kind::ALL.iter().find(|k| k.name() == name) {
+            Some(kind) => Ok(*kind),
+            None => Err(format!("unknown hasher {:?}; use std, xx, fx or a", name)),
+        }
+    }
+}
+
+// Evaluate `body` with `S` standing for the BuildHasher that `kind` names.
+#[macro_export]
+macro_rules! with_hasher {
+    ($kind:expr, |$s:ident| $body:expr) => {
+        match $kind {
+            $crate::Kind::Std => {
+                type $s = $crate::Std;
+                $body
+            }
+            $crate::Kind::Xx => {
+                type $s = $crate::Xx;
+                $body
+            }
+            $crate::Kind::Fx => {
+                type $s = $crate::Fx;
+                $body
+            }
+            $crate::Kind::A => {
+                type $s = $crate::A;
+                $body
+            }
+        }
+    };
+}
+
+// Declare `$hashed<S>`, and optionally `$puzzle`, which solves the puzzle the same way as
+// `$hashed<Std>`.  The second form expects the calling crate to depend on advent.
+#[macro_export]
+macro_rules! hashed {
+    ($hashed:ident) => {
+        pub struct $hashed<S>(::std::marker::PhantomData<S>);
+
+        impl<S> Default for $hashed<S> {
+            fn default() -> $hashed<S> {
+                $hashed(::std::marker::PhantomData)
+            }
+        }
+    };
+    ($hashed:ident, $puzzle:ident) => {
+        $crate::hashed!($hashed);
+
+        pub struct $puzzle;
+
+        impl ::advent::Solution for $puzzle {
+            const DAY: u32 = <$hashed<$crate::Std> as ::advent::Solution>::DAY;
+            type Input<'a> = <$hashed<$crate::Std> as ::advent::Solution>::Input<'a>;
+            type Answer1 = <$hashed<$crate::Std> as ::advent::Solution>::Answer1;
+            type Answer2 = <$hashed<$crate::Std> as ::advent::Solution>::Answer2;
+
+            fn parse(text: &str) -> Result<Self::Input<'_>, ::advent::Error> {
+                <$hashed<$crate::Std> as ::advent::Solution>::parse(text)
+            }
+
+            fn part1(input: &Self::Input<'_>) -> Self::Answer1 {
+                <$hashed<$crate::Std> as ::advent::Solution>::part1(input)
+            }
+
+            fn part2(input: &Self::Input<'_>) -> Self::Answer2 {
+                <$hashed<$crate::Std> as ::advent::Solution>::part2(input)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::hash::BuildHasher;
+
+    fn distinct<S: BuildHasher + Default>(items: &[u64]) -> usize {
+        let set: HashSet<u64, S> = items.iter().copied().collect();
+        return set.len();
+    }
+
+    #[test]
+    fn test_with_hasher() {
+        for &kind in Kind::ALL.iter() {
+            assert_eq!(kind.name().parse::<Kind>(), Ok(kind));
+            assert_eq!(with_hasher!(kind, |S| distinct::<S>(&[1, 2, 2, 3])), 3);
+        }
+        let sizes: Vec<usize> = Kind::ALL
+            .iter()
+            .map(|&kind| with_hasher!(kind, |S| std::mem::size_of::<S>()))
+            .collect();
+        assert_eq!(sizes[1], 0);
+        assert!("md5".parse::<Kind>().is_err());
+    }
+}
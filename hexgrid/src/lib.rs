@@ -0,0 +1,547 @@
+// Coordinates on a grid of hexagons with pointy tops, in rows running east to west.
+//
+// A Hex is in axial coordinates (q, r): q counts steps east and r counts steps north-east.  The
+// third cube coordinate, s = -q - r, is implied, which keeps the six directions symmetric:
+//
+//          nw (-1, 1)   ne (0, 1)
+//     w (-1, 0)    (0, 0)    e (1, 0)
+//          sw (0, -1)   se (1, -1)
+//
+// Puzzles usually draw hexagons as offset rows or columns instead, where every other row (or
+// column) is shifted by half a hexagon.  Offset names those layouts, and converts to and from
+// them.
+
+use advent::Error;
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    E,
+    NE,
+    NW,
+    W,
+    SW,
+    SE,
+}
+
+impl Direction {
+    // Anticlockwise, starting from east.
+    pub const ALL: [Direction; 6] = [
+        Direction::E,
+        Direction::NE,
+        Direction::NW,
+        Direction::W,
+        Direction::SW,
+        Direction::SE,
+    ];
+
+    // One step in this direction.
+    pub fn delta(self) -> Hex {
+        match self {
+            Direction::E => Hex::new(1, 0),
+            Direction::NE => Hex::new(0, 1),
+            Direction::NW => Hex::new(-1, 1),
+            Direction::W => Hex::new(-1, 0),
+            Direction::SW => Hex::new(0, -1),
+            Direction::SE => Hex::new(1, -1),
+        }
+    }
+
+    fn index(self) -> usize {
+        return Direction::ALL.iter().position(|d| *d == self).unwrap();
+    }
+
+    // The next direction anticlockwise, 60° to the left.
+    pub fn left(self) -> Direction {
+        Direction::ALL[(self.index() + 1) % 6]
+    }
+
+    // The next direction clockwise, 60° to the right.
+    pub fn right(self) -> Direction {
+        Direction::ALL[(self.index() + 5) % 6]
+    }
+
+    pub fn opposite(self) -> Direction {
+        Direction::ALL[(self.index() + 3) % 6]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Direction::E => "e",
+            Direction::NE => "ne",
+            Direction::NW => "nw",
+            Direction::W => "w",
+            Direction::SW => "sw",
+            Direction::SE => "se",
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Direction {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Direction, Error> {
+        match Direction::ALL.iter().find(|d| d.name() == text) {
+            Some(d) => Ok(*d),
+            None => Err(Error::parse(1, "expected e, se, sw, w, nw or ne")),
+        }
+    }
+}
+
+// The ways of laying hexagons out in a rectangle of (column, row) offsets.  In the "R" layouts
+// the hexagons have pointy tops and every other row is shifted half a hexagon east: the odd rows
+// in OddR, the even ones in EvenR.  The "Q" layouts are the same turned on their side, with flat
+// tops and every other column shifted half a hexagon north.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Offset {
+    OddR,
+    EvenR,
+    OddQ,
+    EvenQ,
+}
+
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Hex {
+    pub q: isize,
+    pub r: isize,
+}
+
+impl Hex {
+    pub const ORIGIN: Hex = Hex { q: 0, r: 0 };
+
+    pub fn new(q: isize, r: isize) -> Hex {
+        Hex { q, r }
+    }
+
+    // The third cube coordinate, so that q + r + s = 0.
+    pub fn s(self) -> isize {
+        return -self.q - self.r;
+    }
+
+    pub fn from_cube([q, r, s]: [isize; 3]) -> Hex {
+        assert_eq!(q + r + s, 0, "cube coordinates must add up to 0");
+        return Hex { q, r };
+    }
+
+    pub fn cube(self) -> [isize; 3] {
+        return [self.q, self.r, self.s()];
+    }
+
+    pub fn from_offset(layout: Offset, [col, row]: [isize; 2]) -> Hex {
+        match layout {
+            Offset::OddR => Hex::new(col - (row - (row & 1)) / 2, row),
+            Offset::EvenR => Hex::new(col - (row + (row & 1)) / 2, row),
+            Offset::OddQ => Hex::new(col, row - (col - (col & 1)) / 2),
+            Offset::EvenQ => Hex::new(col, row - (col + (col & 1)) / 2),
+        }
+    }
+
+    // [column, row] in the given layout.
+    pub fn offset(self, layout: Offset) -> [isize; 2] {
+        let Hex { q, r } = self;
+        match layout {
+            Offset::OddR => [q + (r - (r & 1)) / 2, r],
+            Offset::EvenR => [q + (r + (r & 1)) / 2, r],
+            Offset::OddQ => [q, r + (q - (q & 1)) / 2],
+            Offset::EvenQ => [q, r + (q + (q & 1)) / 2],
+        }
+    }
+
+    // Follow a path of steps (e, se, sw, w, nw, ne) from here, with no separators between them.
+    pub fn walk(self, path: &str) -> Result<Hex, Error> {
+        let mut p = self;
+        let mut rest = path;
+        while !rest.is_empty() {
+            let len = if rest.starts_with('n') || rest.starts_with('s') {
+                2
+            } else {
+                1
+            };
+            let step = rest.get(..len).unwrap_or(rest);
+            let column = path.len() - rest.len() + 1;
+            let d: Direction = step
+                .parse()
+                .map_err(|_| Error::parse(column, "expected e, se, sw, w, nw or ne"))?;
+            p += d.delta();
+            rest = &rest[step.len()..];
+        }
+        return Ok(p);
+    }
+
+    // Follow a path from the origin.
+    pub fn from_path(path: &str) -> Result<Hex, Error> {
+        Hex::ORIGIN.walk(path)
+    }
+
+    pub fn neighbour(self, d: Direction) -> Hex {
+        self + d.delta()
+    }
+
+    // The six neighbours, in the order of Direction::ALL.
+    pub fn neighbours(self) -> [Hex; 6] {
+        Direction::ALL.map(|d| self.neighbour(d))
+    }
+
+    // The number of steps from the origin.
+    pub fn length(self) -> usize {
+        let [q, r, s] = self.cube();
+        return q.abs().max(r.abs()).max(s.abs()) as usize;
+    }
+
+    // The number of steps between two hexagons.
+    pub fn distance(self, other: Hex) -> usize {
+        (self - other).length()
+    }
+
+    // The hexagons exactly radius steps away, anticlockwise from the south-west corner.
+    pub fn ring(self, radius: usize) -> Vec<Hex> {
+        if radius == 0 {
+            return vec![self];
+        }
+        let mut ring = Vec::with_capacity(6 * radius);
+        let mut p = self + Direction::SW.delta() * radius as isize;
+        for d in Direction::ALL.iter() {
+            for _ in 0..radius {
+                ring.push(p);
+                p += d.delta();
+            }
+        }
+        return ring;
+    }
+
+    // The hexagons at most radius steps away: this one, then each ring outwards.
+    pub fn spiral(self, radius: usize) -> Vec<Hex> {
+        return (0..=radius).flat_map(|n| self.ring(n)).collect();
+    }
+
+    // The hexagons on the straight line from here to other, both ends included.  Where the line
+    // runs exactly along the edge between two hexagons, it always picks the same side.
+    pub fn line_to(self, other: Hex) -> Vec<Hex> {
+        let n = self.distance(other);
+        let [a, b] = [self, other].map(|h| h.cube().map(|c| c as f64));
+        // Nudging the start keeps points off the edges, so that they round the same way.
+        let a = [a[0] + 1e-6, a[1] + 1e-6, a[2] - 2e-6];
+        return (0..=n)
+            .map(|i| {
+                let t = if n == 0 { 0.0 } else { i as f64 / n as f64 };
+                let mut lerp = [0.0; 3];
+                for (l, (a, b)) in lerp.iter_mut().zip(a.iter().zip(b.iter())) {
+                    *l = a + (b - a) * t;
+                }
+                Hex::round(lerp)
+            })
+            .collect();
+    }
+
+    // The hexagon containing a point in (fractional) cube coordinates.
+    fn round(cube: [f64; 3]) -> Hex {
+        let [q, r, s] = cube.map(|c| c.round());
+        let [dq, dr, ds] = [
+            (q - cube[0]).abs(),
+            (r - cube[1]).abs(),
+            (s - cube[2]).abs(),
+        ];
+        // Rounding all three can break q + r + s = 0, so work out the one that moved furthest
+        // from the other two.
+        if dq > dr && dq > ds {
+            return Hex::new((-r - s) as isize, r as isize);
+        } else if dr > ds {
+            return Hex::new(q as isize, (-q - s) as isize);
+        }
+        return Hex::new(q as isize, r as isize);
+    }
+
+    // The centre of this hexagon when drawn with corners size away from its centre, with x
+    // running east and y north.
+    pub fn center(self, size: f64) -> [f64; 2] {
+        let root3 = 3f64.sqrt();
+        let x = size * (root3 * self.q as f64 + root3 / 2.0 * self.r as f64);
+        let y = size * 1.5 * self.r as f64;
+        return [x, y];
+    }
+
+    // The hexagon containing a point, drawn the same way as center().
+    pub fn at_point([x, y]: [f64; 2], size: f64) -> Hex {
+        let q = (3f64.sqrt() / 3.0 * x - y / 3.0) / size;
+        let r = (2.0 / 3.0 * y) / size;
+        return Hex::round([q, r, -q - r]);
+    }
+
+    // Rotate 60° anticlockwise around the origin.
+    pub fn rotate_left(self) -> Hex {
+        let [q, r, s] = self.cube();
+        return Hex::from_cube([-r, -s, -q]);
+    }
+
+    // Rotate 60° clockwise around the origin.
+    pub fn rotate_right(self) -> Hex {
+        let [q, r, s] = self.cube();
+        return Hex::from_cube([-s, -q, -r]);
+    }
+
+    // Rotate 60° anticlockwise around center.
+    pub fn rotate_left_around(self, center: Hex) -> Hex {
+        (self - center).rotate_left() + center
+    }
+
+    // Rotate 60° clockwise around center.
+    pub fn rotate_right_around(self, center: Hex) -> Hex {
+        (self - center).rotate_right() + center
+    }
+
+    // Reflect in the east-west line through the origin, swapping north and south.
+    pub fn reflect_ns(self) -> Hex {
+        let [q, r, s] = self.cube();
+        return Hex::from_cube([-s, -r, -q]);
+    }
+
+    // Reflect by swapping two of the cube coordinates: reflect_q keeps q and swaps r with s, and
+    // so on.  Each one is a mirror through the origin along a line of hexagon corners.
+    pub fn reflect_q(self) -> Hex {
+        let [q, r, s] = self.cube();
+        return Hex::from_cube([q, s, r]);
+    }
+
+    pub fn reflect_r(self) -> Hex {
+        let [q, r, s] = self.cube();
+        return Hex::from_cube([s, r, q]);
+    }
+
+    pub fn reflect_s(self) -> Hex {
+        let [q, r, s] = self.cube();
+        return Hex::from_cube([r, q, s]);
+    }
+}
+
+impl fmt::Display for Hex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.q, self.r)
+    }
+}
+
+impl Add for Hex {
+    type Output = Hex;
+
+    fn add(self, other: Hex) -> Hex {
+        Hex::new(self.q + other.q, self.r + other.r)
+    }
+}
+
+impl AddAssign for Hex {
+    fn add_assign(&mut self, other: Hex) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Hex {
+    type Output = Hex;
+
+    fn sub(self, other: Hex) -> Hex {
+        Hex::new(self.q - other.q, self.r - other.r)
+    }
+}
+
+impl SubAssign for Hex {
+    fn sub_assign(&mut self, other: Hex) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Hex {
+    type Output = Hex;
+
+    fn neg(self) -> Hex {
+        Hex::new(-self.q, -self.r)
+    }
+}
+
+impl Mul<isize> for Hex {
+    type Output = Hex;
+
+    fn mul(self, n: isize) -> Hex {
+        Hex::new(self.q * n, self.r * n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directions() {
+        for d in Direction::ALL.iter() {
+            assert_eq!(d.delta().length(), 1);
+            assert_eq!(d.delta() + d.opposite().delta(), Hex::ORIGIN);
+            assert_eq!(d.left().right(), *d);
+            assert_eq!(d.delta().rotate_left(), d.left().delta());
+            assert_eq!(d.delta().rotate_right(), d.right().delta());
+            assert_eq!(d.name().parse::<Direction>().unwrap(), *d);
+        }
+        assert!("n".parse::<Direction>().is_err());
+    }
+
+    #[test]
+    fn test_paths() {
+        assert_eq!(Hex::from_path("").unwrap(), Hex::ORIGIN);
+        assert_eq!(Hex::from_path("nwwswee").unwrap(), Hex::ORIGIN);
+        assert_eq!(Hex::from_path("esew").unwrap(), Hex::new(1, -1));
+        assert_eq!(Hex::from_path("nene").unwrap(), Hex::new(0, 2));
+        let error = |path: &str| Hex::from_path(path).unwrap_err().to_string();
+        assert_eq!(
+            error("nex"),
+            "line 1, column 3: expected e, se, sw, w, nw or ne"
+        );
+        assert_eq!(
+            error("eesn"),
+            "line 1, column 3: expected e, se, sw, w, nw or ne"
+        );
+        assert_eq!(
+            error("s"),
+            "line 1, column 1: expected e, se, sw, w, nw or ne"
+        );
+    }
+
+    #[test]
+    fn test_offsets() {
+        let layouts = [Offset::OddR, Offset::EvenR, Offset::OddQ, Offset::EvenQ];
+        for &layout in layouts.iter() {
+            for row in -3..=3 {
+                for col in -3..=3 {
+                    let h = Hex::from_offset(layout, [col, row]);
+                    assert_eq!(h.offset(layout), [col, row]);
+                }
+            }
+        }
+        // Day 24 laid its floor out as OddR, where moving north-east from an odd row also
+        // moves a column east, but from an even row stays in the same column.
+        let ne = |col, row| {
+            let h = Hex::from_offset(Offset::OddR, [col, row]);
+            return h.neighbour(Direction::NE).offset(Offset::OddR);
+        };
+        assert_eq!(ne(1, 1), [2, 2]);
+        assert_eq!(ne(2, 2), [2, 3]);
+        // Each row's east-west neighbours are the next columns along in the R layouts, and each
+        // column's north-south neighbours the next rows along in the Q layouts.
+        let h = Hex::new(2, -3);
+        let [col, row] = h.offset(Offset::EvenR);
+        assert_eq!(
+            h.neighbour(Direction::E).offset(Offset::EvenR),
+            [col + 1, row]
+        );
+        let [col, row] = h.offset(Offset::OddQ);
+        let north = Hex::from_offset(Offset::OddQ, [col, row + 1]);
+        assert_eq!(north.distance(h), 1);
+    }
+
+    #[test]
+    fn test_distance() {
+        let h = Hex::new(3, -1);
+        assert_eq!(h.length(), 3);
+        assert_eq!(h.distance(Hex::new(-2, 4)), 5);
+        assert_eq!(h.distance(h), 0);
+        assert_eq!(Hex::from_cube(h.cube()), h);
+    }
+
+    #[test]
+    fn test_points() {
+        for h in Hex::new(2, -1).spiral(3) {
+            let [x, y] = h.center(10.0);
+            assert_eq!(Hex::at_point([x, y], 10.0), h);
+            // Just inside each corner is still the same hexagon.
+            for corner in 0..6 {
+                let angle = (30.0 + 60.0 * corner as f64).to_radians();
+                let point = [x + 9.5 * angle.cos(), y + 9.5 * angle.sin()];
+                assert_eq!(Hex::at_point(point, 10.0), h);
+            }
+        }
+        let [x, y] = Direction::NE.delta().center(1.0);
+        assert!(x > 0.0 && y > 0.0);
+    }
+
+    #[test]
+    fn test_rings() {
+        let center = Hex::new(1, 2);
+        assert_eq!(center.ring(0), vec![center]);
+        for radius in 1..5 {
+            let ring = center.ring(radius);
+            assert_eq!(ring.len(), 6 * radius);
+            assert!(ring.iter().all(|h| h.distance(center) == radius));
+            // Each hexagon is next to the one before it, all the way round.
+            for (a, b) in ring.iter().zip(ring.iter().cycle().skip(1)) {
+                assert_eq!(a.distance(*b), 1);
+            }
+        }
+        let mut spiral = center.spiral(3);
+        assert_eq!(spiral.len(), 37);
+        spiral.sort();
+        spiral.dedup();
+        assert_eq!(spiral.len(), 37);
+    }
+
+    #[test]
+    fn test_lines() {
+        let a = Hex::new(-2, 1);
+        assert_eq!(a.line_to(a), vec![a]);
+        for b in Hex::new(1, -1).spiral(4) {
+            let line = a.line_to(b);
+            assert_eq!(line.len(), a.distance(b) + 1);
+            assert_eq!((line[0], line[line.len() - 1]), (a, b));
+            for pair in line.windows(2) {
+                assert_eq!(pair[0].distance(pair[1]), 1);
+            }
+        }
+        let east: Vec<Hex> = (0..4).map(|q| Hex::new(q, 0)).collect();
+        assert_eq!(Hex::ORIGIN.line_to(Hex::new(3, 0)), east);
+    }
+
+    #[test]
+    fn test_rotation() {
+        let h = Hex::new(3, -1);
+        let mut p = h;
+        for _ in 0..6 {
+            assert_eq!(p.length(), h.length());
+            p = p.rotate_left();
+        }
+        assert_eq!(p, h);
+        assert_eq!(h.rotate_left().rotate_right(), h);
+        let center = Hex::new(1, 1);
+        let e = center.neighbour(Direction::E);
+        assert_eq!(
+            e.rotate_left_around(center),
+            center.neighbour(Direction::NE)
+        );
+        assert_eq!(
+            e.rotate_right_around(center),
+            center.neighbour(Direction::SE)
+        );
+    }
+
+    #[test]
+    fn test_reflection() {
+        assert_eq!(Direction::NE.delta().reflect_ns(), Direction::SE.delta());
+        assert_eq!(Direction::E.delta().reflect_ns(), Direction::E.delta());
+        let h = Hex::new(3, -1);
+        for reflect in [
+            Hex::reflect_ns,
+            Hex::reflect_q,
+            Hex::reflect_r,
+            Hex::reflect_s,
+        ]
+        .iter()
+        {
+            assert_eq!(reflect(reflect(h)), h);
+            assert_eq!(reflect(h).length(), h.length());
+        }
+        assert_eq!(h.reflect_q().q, h.q);
+        assert_eq!(h.reflect_r().r, h.r);
+        assert_eq!(h.reflect_s().s(), h.s());
+    }
+}
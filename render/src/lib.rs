@@ -0,0 +1,417 @@
+// Pictures of cellular automata, for seeing what a set of rules actually did.
+//
+// Each generation becomes an Image, and save() writes the lot: one PPM or PNG file per frame, or a
+// single animated GIF, depending on the file name.  The drawing functions take every generation
+// at once, so that all the frames share one bounding box and line up in an animation:
+//
+//   text      a character grid, such as day 11's seats
+//   hexes     hexagonal cells, such as day 24's black tiles
+//   slices    N-dimensional cells, such as day 17's cubes, as a grid of 2D slices
+
+use hexgrid::Hex;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+pub type Rgb = [u8; 3];
+
+// Live cells, dead cells, and the lines and gaps between them.
+pub const LIVE: Rgb = [32, 32, 32];
+pub const DEAD: Rgb = [240, 240, 240];
+pub const GAP: Rgb = [176, 176, 176];
+
+// How long each frame of an animated GIF stays up, in hundredths of a second.
+pub const DELAY: u16 = 20;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Image {
+        Image {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        return self.pixels[y * self.width + x];
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, color: Rgb) {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is off the image",
+            x,
+            y
+        );
+        self.pixels[y * self.width + x] = color;
+    }
+
+    // Fill a rectangle, clipped to the image.
+    pub fn fill(&mut self, x: usize, y: usize, width: usize, height: usize, color: Rgb) {
+        for y in y..(y + height).min(self.height) {
+            for x in x..(x + width).min(self.width) {
+                self.pixels[y * self.width + x] = color;
+            }
+        }
+    }
+
+    fn bytes(&self) -> Vec<u8> {
+        return self.pixels.iter().flatten().copied().collect();
+    }
+
+    // A binary PPM (P6), which nearly anything can read and is trivial to write.
+    pub fn write_ppm(&self, mut w: impl Write) -> io::Result<()> {
+        write!(w, "P6\n{} {}\n255\n", self.width, self.height)?;
+        w.write_all(&self.bytes())?;
+        return w.flush();
+    }
+
+    pub fn write_png(&self, w: impl Write) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(w, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.bytes())?;
+        return Ok(writer.finish()?);
+    }
+}
+
+fn gif_error(e: gif::EncodingError) -> io::Error {
+    match e {
+        gif::EncodingError::Io(e) => e,
+        gif::EncodingError::Format(e) => io::Error::new(io::ErrorKind::InvalidData, e),
+    }
+}
+
+// Frames as an animated GIF that loops forever.  GIFs are indexed, so all the frames together
+// can only have 256 colours, which is plenty for these.
+pub fn write_gif(frames: &[Image], delay: u16, w: impl Write) -> io::Result<()> {
+    let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+    let (width, height) = match frames.first() {
+        Some(f) => (f.width, f.height),
+        None => return Err(invalid("an animation needs at least one frame".to_string())),
+    };
+    if frames
+        .iter()
+        .any(|f| (f.width, f.height) != (width, height))
+    {
+        return Err(invalid("the frames must all be the same size".to_string()));
+    }
+    if width > u16::MAX as usize || height > u16::MAX as usize {
+        return Err(invalid(format!(
+            "{}x{} is too big for a GIF",
+            width, height
+        )));
+    }
+    let mut colors: Vec<Rgb> = frames
+        .iter()
+        .flat_map(|f| f.pixels.iter().copied())
+        .collect();
+    colors.sort_unstable();
+    colors.dedup();
+    if colors.len() > 256 {
+        return Err(invalid(format!(
+            "{} colours won't fit in a GIF",
+            colors.len()
+        )));
+    }
+    let palette: Vec<u8> = colors.iter().flatten().copied().collect();
+    let mut encoder =
+        gif::Encoder::new(w, width as u16, height as u16, &palette).map_err(gif_error)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(gif_error)?;
+    for f in frames {
+        let indices: Vec<u8> = f
+            .pixels
+            .iter()
+            .map(|p| colors.binary_search(p).unwrap() as u8)
+            .collect();
+        let frame = gif::Frame {
+            width: width as u16,
+            height: height as u16,
+            delay,
+            buffer: indices.into(),
+            ..gif::Frame::default()
+        };
+        encoder.write_frame(&frame).map_err(gif_error)?;
+    }
+    return Ok(());
+}
+
+// The name of frame i of n: just `path` for a single frame, or else with the frame number
+// before the extension, as in "floor-007.png".
+fn frame_path(path: &Path, i: usize, n: usize) -> PathBuf {
+    if n == 1 {
+        return path.to_path_buf();
+    }
+    let digits = (n - 1).to_string().len().max(3);
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let mut name = format!("{}-{:0width$}", stem, i, width = digits);
+    if let Some(ext) = path.extension() {
+        name = format!("{}.{}", name, ext.to_string_lossy());
+    }
+    return path.with_file_name(name);
+}
+
+// Write frames to `path`, which ends in .gif (one animation), .png or .ppm (a file per frame).
+// Returns the files written.
+pub fn save(path: &Path, frames: &[Image]) -> io::Result<Vec<PathBuf>> {
+    let ext = path.extension().map(|e| e.to_string_lossy().to_lowercase());
+    let create = |path: &Path| File::create(path).map(BufWriter::new);
+    match ext.as_deref() {
+        Some("gif") => {
+            write_gif(frames, DELAY, create(path)?)?;
+            return Ok(vec![path.to_path_buf()]);
+        }
+        Some("png") | Some("ppm") => {
+            let mut written = Vec::new();
+            for (i, frame) in frames.iter().enumerate() {
+                let p = frame_path(path, i, frames.len());
+                match ext.as_deref() {
+                    Some("png") => frame.write_png(create(&p)?)?,
+                    _ => frame.write_ppm(create(&p)?)?,
+                }
+                written.push(p);
+            }
+            return Ok(written);
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{}: expected a .gif, .png or .ppm file", path.display()),
+        )),
+    }
+}
+
+// What save() wrote, for telling the user: "floor.gif", or "floor-000.png to floor-100.png".
+pub fn describe(files: &[PathBuf]) -> String {
+    match files {
+        [] => "nothing".to_string(),
+        [file] => file.display().to_string(),
+        [first, .., last] => format!("{} to {}", first.display(), last.display()),
+    }
+}
+
+// Each generation of a character grid, with every character a square of `scale` pixels
+// coloured by `color`.
+pub fn text(generations: &[String], scale: usize, color: impl Fn(char) -> Rgb) -> Vec<Image> {
+    let rows = |text: &String| text.lines().count();
+    let cols = |text: &String| text.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+    let height = generations.iter().map(rows).max().unwrap_or(0);
+    let width = generations.iter().map(cols).max().unwrap_or(0);
+    return generations
+        .iter()
+        .map(|text| {
+            let mut image = Image::new(width * scale, height * scale, GAP);
+            for (y, line) in text.lines().enumerate() {
+                for (x, ch) in line.chars().enumerate() {
+                    image.fill(x * scale, y * scale, scale, scale, color(ch));
+                }
+            }
+            image
+        })
+        .collect();
+}
+
+// Each generation of live hexagons, north up, drawn with corners `size` pixels from their
+// centres.  Every hexagon inside the frame is drawn, live or dead, with a gap around it.
+pub fn hexes(generations: &[Vec<Hex>], size: usize) -> Vec<Image> {
+    let size = size.max(2) as f64;
+    let mut centers = generations.iter().flatten().map(|h| h.center(size));
+    let first = centers.next().unwrap_or([0.0, 0.0]);
+    let (mut min, mut max) = (first, first);
+    for [x, y] in centers {
+        min = [min[0].min(x), min[1].min(y)];
+        max = [max[0].max(x), max[1].max(y)];
+    }
+    let margin = 2.0 * size;
+    let width = (max[0] - min[0] + 2.0 * margin).ceil() as usize;
+    let height = (max[1] - min[1] + 2.0 * margin).ceil() as usize;
+    // The point in the middle of pixel (px, py), flipped so that y runs north.
+    let point = |px: usize, py: usize| {
+        let x = min[0] - margin + px as f64 + 0.5;
+        let y = max[1] + margin - py as f64 - 0.5;
+        return [x, y];
+    };
+    // Which hexagon each pixel is in, or None for the gaps between them: the pixels that land in
+    // another hexagon once pushed a little further out from their own hexagon's centre.
+    let mut cells: Vec<Option<Hex>> = Vec::with_capacity(width * height);
+    for py in 0..height {
+        for px in 0..width {
+            let [x, y] = point(px, py);
+            let h = Hex::at_point([x, y], size);
+            let [cx, cy] = h.center(size);
+            let pushed = [cx + (x - cx) * 1.15, cy + (y - cy) * 1.15];
+            cells.push(Some(h).filter(|h| Hex::at_point(pushed, size) == *h));
+        }
+    }
+    return generations
+        .iter()
+        .map(|live| {
+            let live: std::collections::HashSet<&Hex> = live.iter().collect();
+            let mut image = Image::new(width, height, GAP);
+            for (pixel, cell) in image.pixels.iter_mut().zip(cells.iter()) {
+                if let Some(h) = cell {
+                    *pixel = if live.contains(h) { LIVE } else { DEAD };
+                }
+            }
+            image
+        })
+        .collect();
+}
+
+// Each generation of live cells in D dimensions, as a grid of 2D slices through x and y, with
+// cells `scale` pixels across.  Slices along the third axis go across the grid and, in 4 or more
+// dimensions, each combination of the rest gets a row of its own.
+pub fn slices<const D: usize>(generations: &[Vec<[isize; D]>], scale: usize) -> Vec<Image> {
+    let mut min = [0isize; D];
+    let mut max = [0isize; D];
+    for (i, cell) in generations.iter().flatten().enumerate() {
+        for axis in 0..D {
+            min[axis] = if i == 0 {
+                cell[axis]
+            } else {
+                min[axis].min(cell[axis])
+            };
+            max[axis] = if i == 0 {
+                cell[axis]
+            } else {
+                max[axis].max(cell[axis])
+            };
+        }
+    }
+    let size: Vec<usize> = (0..D).map(|a| (max[a] - min[a] + 1) as usize).collect();
+    let extent = |axis: usize| size.get(axis).copied().unwrap_or(1);
+    let across = extent(2);
+    let down: usize = (3..D).map(extent).product();
+    // Each slice is surrounded by a gap one cell wide.
+    let (tile_w, tile_h) = (extent(0) + 1, extent(1) + 1);
+    let width = (across * tile_w + 1) * scale;
+    let height = (down * tile_h + 1) * scale;
+    let blank = {
+        let mut image = Image::new(width, height, GAP);
+        for row in 0..down {
+            for col in 0..across {
+                let (x, y) = ((col * tile_w + 1) * scale, (row * tile_h + 1) * scale);
+                image.fill(x, y, extent(0) * scale, extent(1) * scale, DEAD);
+            }
+        }
+        image
+    };
+    return generations
+        .iter()
+        .map(|live| {
+            let mut image = blank.clone();
+            for cell in live {
+                let offset = |axis: usize| (cell[axis] - min[axis]) as usize;
+                let col = if D > 2 { offset(2) } else { 0 };
+                let row = (3..D).fold(0, |row, axis| row * extent(axis) + offset(axis));
+                // North up: y grows upwards in the slice, but downwards in the image.
+                let x = col * tile_w + 1 + offset(0);
+                let y = row * tile_h + 1 + (extent(1) - 1 - offset(1));
+                image.fill(x * scale, y * scale, scale, scale, LIVE);
+            }
+            image
+        })
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formats() {
+        let mut image = Image::new(3, 2, DEAD);
+        image.set(2, 1, LIVE);
+        let mut ppm = Vec::new();
+        image.write_ppm(&mut ppm).unwrap();
+        assert!(ppm.starts_with(b"P6\n3 2\n255\n"));
+        assert_eq!(ppm.len(), 11 + 3 * 2 * 3);
+        assert_eq!(&ppm[ppm.len() - 3..], &LIVE);
+
+        let mut png = Vec::new();
+        image.write_png(&mut png).unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+
+        let mut gif = Vec::new();
+        write_gif(&[image.clone(), Image::new(3, 2, LIVE)], DELAY, &mut gif).unwrap();
+        assert!(gif.starts_with(b"GIF89a"));
+        assert!(write_gif(&[image, Image::new(2, 2, LIVE)], DELAY, &mut gif).is_err());
+        assert!(write_gif(&[], DELAY, &mut gif).is_err());
+    }
+
+    #[test]
+    fn test_frame_paths() {
+        let path = Path::new("out/floor.png");
+        assert_eq!(frame_path(path, 0, 1), path);
+        assert_eq!(frame_path(path, 7, 101), Path::new("out/floor-007.png"));
+        assert_eq!(frame_path(path, 7, 5000), Path::new("out/floor-0007.png"));
+        assert!(save(Path::new("floor.jpg"), &[]).is_err());
+        let files = [frame_path(path, 0, 3), frame_path(path, 2, 3)];
+        assert_eq!(describe(&files), "out/floor-000.png to out/floor-002.png");
+    }
+
+    #[test]
+    fn test_text() {
+        let generations = vec!["L.\n#L".to_string(), "#.\n##".to_string()];
+        let frames = text(&generations, 2, |ch| match ch {
+            '#' => LIVE,
+            'L' => DEAD,
+            _ => GAP,
+        });
+        assert_eq!(frames.len(), 2);
+        assert_eq!((frames[0].width(), frames[0].height()), (4, 4));
+        assert_eq!(frames[0].get(0, 0), DEAD);
+        assert_eq!(frames[0].get(3, 0), GAP);
+        assert_eq!(frames[0].get(1, 3), LIVE);
+        assert_eq!(frames[1].get(1, 1), LIVE);
+    }
+
+    #[test]
+    fn test_hexes() {
+        // The frame is centred on the only hexagon there ever was.
+        let frames = hexes(&[vec![Hex::new(1, 1)], vec![]], 8);
+        let (px, py) = (frames[0].width() / 2, frames[0].height() / 2);
+        assert_eq!(frames[0].get(px, py), LIVE);
+        assert_eq!(frames[1].get(px, py), DEAD);
+        assert_eq!(frames[0].get(0, 0), DEAD);
+        assert!(frames[0].pixels.contains(&GAP));
+    }
+
+    #[test]
+    fn test_slices() {
+        // Two cells in different z slices of a 3D grid, and then just one.
+        let frames = slices::<3>(&[vec![[0, 0, 0], [1, 1, 1]], vec![[0, 0, 0]]], 1);
+        // Two 2x2 slices side by side, with gaps around them.
+        assert_eq!((frames[0].width(), frames[0].height()), (7, 4));
+        // [0, 0, 0] is at the bottom left of the first slice, and [1, 1, 1] the top right of
+        // the second.
+        assert_eq!(frames[0].get(1, 2), LIVE);
+        assert_eq!(frames[0].get(5, 1), LIVE);
+        assert_eq!(frames[0].get(4, 2), DEAD);
+        assert_eq!(frames[0].get(3, 1), GAP);
+        assert_eq!(frames[1].get(5, 1), DEAD);
+
+        // In 4D, w picks the row of slices.
+        let frames = slices::<4>(&[vec![[0, 0, 0, 0], [0, 0, 0, 1]]], 1);
+        assert_eq!((frames[0].width(), frames[0].height()), (3, 5));
+        assert_eq!(frames[0].get(1, 1), LIVE);
+        assert_eq!(frames[0].get(1, 3), LIVE);
+    }
+}
@@ -0,0 +1,122 @@
+// Recorded answers, so the runner can tell whether a day still solves its input correctly.
+//
+// Each day keeps them next to its input.txt, in answers.toml:
+//
+//   day = 5
+//   part1 = 835
+//   part2 = 649
+//
+// Answers may be written as integers or strings; either way they're compared with the
+// solver's answer as text.  A part with no recorded answer is UNKNOWN.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use toml::{Table, Value};
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub enum Verdict {
+    Pass,
+    Fail(String),
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail(expected) => write!(f, "FAIL, expected {}", expected),
+            Verdict::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+impl Answers {
+    // Parse an answers file for `day`.  `name` is only used in error messages.
+    pub fn parse(day: u32, name: &str, text: &str) -> Result<Answers, String> {
+        let table: Table = text.parse().map_err(|e| format!("{}: {}", name, e))?;
+        let mut answers = Answers::default();
+        for (key, value) in &table {
+            match key.as_str() {
+                "day" => match value.as_integer() {
+                    Some(n) if n == day as i64 => (),
+                    _ => return Err(format!("{}: answers are for day {}", name, value)),
+                },
+                "part1" => answers.part1 = Some(answer_text(name, key, value)?),
+                "part2" => answers.part2 = Some(answer_text(name, key, value)?),
+                _ => return Err(format!("{}: unexpected key {:?}", name, key)),
+            }
+        }
+        Ok(answers)
+    }
+
+    // Load the answers for `day` from `path`.  A missing file just means nothing is known yet.
+    pub fn load(day: u32, path: &Path) -> Result<Answers, String> {
+        let name = path.display().to_string();
+        match fs::read_to_string(path) {
+            Ok(text) => Answers::parse(day, &name, &text),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(format!("{}: {}", name, e)),
+        }
+    }
+
+    pub fn check(&self, part: u32, answer: &str) -> Verdict {
+        let expected = match part {
+            1 => &self.part1,
+            _ => &self.part2,
+        };
+        match expected {
+            None => Verdict::Unknown,
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail(expected.clone()),
+        }
+    }
+}
+
+fn answer_text(name: &str, key: &str, value: &Value) -> Result<String, String> {
+    match value {
+        Value::String(s) => Ok(s.clone()),
+        Value::Integer(n) => Ok(n.to_string()),
+        _ => Err(format!(
+            "{}: {} must be a string or an integer, got {}",
+            name, key, value
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::parse(21, "t", "day = 21\npart1 = 5\npart2 = \"a,b\"\n").unwrap();
+        assert_eq!(answers.check(1, "5"), Verdict::Pass);
+        assert_eq!(answers.check(1, "6"), Verdict::Fail("5".to_string()));
+        assert_eq!(answers.check(2, "a,b"), Verdict::Pass);
+
+        let answers = Answers::parse(21, "t", "day = 21\npart1 = 5\n").unwrap();
+        assert_eq!(answers.check(2, "a,b"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = Answers::parse(3, "t", "day = 4\npart1 = 1\n").unwrap_err();
+        assert_eq!(e, "t: answers are for day 4");
+        let e = Answers::parse(3, "t", "day = 3\npart3 = 1\n").unwrap_err();
+        assert_eq!(e, "t: unexpected key \"part3\"");
+        assert!(Answers::parse(3, "t", "day = 3\npart1 = [1]\n").is_err());
+        assert_eq!(
+            Answers::load(3, Path::new("no/such/answers.toml")),
+            Ok(Answers::default())
+        );
+    }
+}
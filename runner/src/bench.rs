@@ -0,0 +1,303 @@
+// advent bench: times parse, part 1 and part 2 of each day separately.
+//
+//   advent bench [day|all] [--runs n] [--baseline path] [--save path] [--hashers]
+//
+// Each day is run up to --runs times (but stops early once it has used up its time budget) and
+// the fastest time of each stage is reported.  --save records the results as JSON, and
+// --baseline compares against results saved earlier, flagging anything that got much slower.
+//
+// --hashers instead runs each of the days with big hash tables once with every hasher in the
+// hashers crate, a row per day and hasher.
+
+use crate::{parse_number, read_input};
+use advent::{Day, Timings};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+// Stop repeating a day once its runs have taken this long.
+const BUDGET: Duration = Duration::from_secs(1);
+
+// A stage this much slower than the baseline is a regression.
+const THRESHOLD: f64 = 0.2;
+
+pub struct BenchArgs {
+    days: Vec<u32>,
+    runs: u32,
+    baseline: Option<PathBuf>,
+    save: Option<PathBuf>,
+    hashers: bool,
+}
+
+// Times in nanoseconds, as saved in a baseline file.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+struct Record {
+    parse_ns: u64,
+    part1_ns: u64,
+    part2_ns: u64,
+}
+
+impl Record {
+    fn from_timings(t: &Timings) -> Record {
+        Record {
+            parse_ns: t.parse.as_nanos() as u64,
+            part1_ns: t.part1.as_nanos() as u64,
+            part2_ns: t.part2.as_nanos() as u64,
+        }
+    }
+
+    fn stages(&self) -> [u64; 3] {
+        [self.parse_ns, self.part1_ns, self.part2_ns]
+    }
+
+    fn add(&self, other: &Record) -> Record {
+        Record {
+            parse_ns: self.parse_ns + other.parse_ns,
+            part1_ns: self.part1_ns + other.part1_ns,
+            part2_ns: self.part2_ns + other.part2_ns,
+        }
+    }
+}
+
+type Baseline = BTreeMap<u32, Record>;
+
+pub fn parse_bench_args(args: &[String]) -> Result<BenchArgs, String> {
+    let mut bench = BenchArgs {
+        days: (1..=25).collect(),
+        runs: 10,
+        baseline: None,
+        save: None,
+        hashers: false,
+    };
+    let mut day: Option<&str> = None;
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        let mut value = |flag: &str| match it.next() {
+            Some(value) => Ok(value.as_str()),
+            None => Err(format!("{} needs a value", flag)),
+        };
+        match arg.as_str() {
+            "--runs" | "-n" => bench.runs = parse_number(value(arg)?, "runs", 1000)?,
+            "--baseline" | "-b" => bench.baseline = Some(PathBuf::from(value(arg)?)),
+            "--save" | "-s" => bench.save = Some(PathBuf::from(value(arg)?)),
+            "--hashers" => bench.hashers = true,
+            _ if day.is_none() => day = Some(arg),
+            _ => return Err(format!("unexpected argument {:?}", arg)),
+        }
+    }
+    match day {
+        None | Some("all") => (),
+        Some(day) => bench.days = vec![parse_number(day, "day", 25)?],
+    }
+    if bench.hashers && (bench.baseline.is_some() || bench.save.is_some()) {
+        return Err("--hashers can't be used with --baseline or --save".to_string());
+    }
+    Ok(bench)
+}
+
+// The fastest time of each stage over up to `runs` runs.
+fn measure(day: u32, runs: u32) -> Result<(Record, u32), String> {
+    let solution = crate::days::find(day).ok_or(format!("day {} is not registered", day))?;
+    measure_solution(solution, runs)
+}
+
+fn measure_solution(solution: &dyn Day, runs: u32) -> Result<(Record, u32), String> {
+    let day = solution.day();
+    let input = read_input(day, &None)?;
+    let start = Instant::now();
+    let mut best: Option<Record> = None;
+    let mut count = 0;
+    while count < runs && (count == 0 || start.elapsed() < BUDGET) {
+        let timings = solution
+            .time(&input)
+            .map_err(|e| format!("day {}: {}", day, e.report(&input)))?;
+        let r = Record::from_timings(&timings);
+        best = Some(match best {
+            None => r,
+            Some(b) => Record {
+                parse_ns: b.parse_ns.min(r.parse_ns),
+                part1_ns: b.part1_ns.min(r.part1_ns),
+                part2_ns: b.part2_ns.min(r.part2_ns),
+            },
+        });
+        count += 1;
+    }
+    Ok((best.unwrap(), count))
+}
+
+fn load_baseline(path: &PathBuf) -> Result<Baseline, String> {
+    let name = path.display();
+    let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", name, e))?;
+    serde_json::from_str(&text).map_err(|e| format!("{}: {}", name, e))
+}
+
+// One cell of the table: the time, and how it compares to the baseline.
+fn cell(ns: u64, base: Option<u64>, regressions: &mut u32) -> String {
+    let time = format!("{:.3?}", Duration::from_nanos(ns));
+    let base = match base {
+        Some(base) if base > 0 => base,
+        _ => return format!("{:>12}", time),
+    };
+    let change = ns as f64 / base as f64 - 1.0;
+    let flag = if change > THRESHOLD {
+        *regressions += 1;
+        "!"
+    } else {
+        " "
+    };
+    format!("{:>12} {:>+5.0}%{}", time, change * 100.0, flag)
+}
+
+fn row(label: &str, record: &Record, base: Option<&Record>, runs: &str, regressions: &mut u32) {
+    let base = base.map(|b| b.stages());
+    let cells: Vec<String> = record
+        .stages()
+        .iter()
+        .enumerate()
+        .map(|(i, &ns)| cell(ns, base.map(|b| b[i]), regressions))
+        .collect();
+    println!("{:>5} {} {:>5}", label, cells.join(" "), runs);
+}
+
+// A row for each hasher on each of the days that have a hashed version.
+fn bench_hashers(args: &BenchArgs) -> Result<(), String> {
+    println!(
+        "{:>5} {:>6} {:>12} {:>12} {:>12} {:>5}",
+        "day", "hasher", "parse", "part 1", "part 2", "runs"
+    );
+    for &day in &args.days {
+        for &kind in hashers::Kind::ALL.iter() {
+            let solution = match crate::days::hashed(day, kind) {
+                Some(solution) => solution,
+                None => break,
+            };
+            let (record, runs) = measure_solution(&*solution, args.runs)?;
+            let cells: Vec<String> = record
+                .stages()
+                .iter()
+                .map(|&ns| cell(ns, None, &mut 0))
+                .collect();
+            println!("{:>5} {:>6} {} {:>5}", day, kind, cells.join(" "), runs);
+        }
+    }
+    Ok(())
+}
+
+pub fn bench(args: &BenchArgs) -> Result<(), String> {
+    if args.hashers {
+        return bench_hashers(args);
+    }
+    let baseline = match &args.baseline {
+        Some(path) => Some(load_baseline(path)?),
+        None => None,
+    };
+    let width = if baseline.is_some() { 20 } else { 12 };
+    println!(
+        "{:>5} {:>w$} {:>w$} {:>w$} {:>5}",
+        "day",
+        "parse",
+        "part 1",
+        "part 2",
+        "runs",
+        w = width
+    );
+
+    let mut results = Baseline::new();
+    let mut total = Record::default();
+    let mut regressions = 0;
+    for &day in &args.days {
+        let (record, runs) = measure(day, args.runs)?;
+        let base = baseline.as_ref().and_then(|b| b.get(&day));
+        row(
+            &day.to_string(),
+            &record,
+            base,
+            &runs.to_string(),
+            &mut regressions,
+        );
+        total = total.add(&record);
+        results.insert(day, record);
+    }
+    if args.days.len() > 1 {
+        // Only compare totals over the days the baseline knows about.
+        let base_total = baseline.as_ref().map(|b| {
+            results
+                .keys()
+                .filter_map(|day| b.get(day))
+                .fold(Record::default(), |sum, r| sum.add(r))
+        });
+        row("total", &total, base_total.as_ref(), "", &mut 0);
+    }
+    if baseline.is_some() && regressions > 0 {
+        eprintln!(
+            "{} stage(s) more than {:.0}% slower than the baseline",
+            regressions,
+            THRESHOLD * 100.0
+        );
+    }
+
+    if let Some(path) = &args.save {
+        // Merge into what's already saved, so benchmarking one day doesn't forget the others.
+        let mut saved = if path.exists() {
+            load_baseline(path)?
+        } else {
+            Baseline::new()
+        };
+        saved.extend(results);
+        let json = serde_json::to_string_pretty(&saved).map_err(|e| e.to_string())?;
+        fs::write(path, json + "\n").map_err(|e| format!("{}: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cell() {
+        let mut regressions = 0;
+        assert_eq!(cell(1500, None, &mut regressions), "     1.500µs");
+        assert_eq!(
+            cell(1500, Some(1000), &mut regressions),
+            "     1.500µs   +50%!"
+        );
+        assert_eq!(
+            cell(900, Some(1000), &mut regressions),
+            "   900.000ns   -10% "
+        );
+        assert_eq!(regressions, 1);
+    }
+
+    #[test]
+    fn test_hashers_args() {
+        let args = |line: &str| {
+            let args: Vec<String> = line.split_whitespace().map(String::from).collect();
+            parse_bench_args(&args)
+        };
+        let bench = args("17 --hashers").unwrap();
+        assert!(bench.hashers);
+        assert_eq!(bench.days, [17]);
+        assert!(args("--hashers --save bench.json").is_err());
+        assert!(crate::days::hashed(15, hashers::Kind::Fx).is_some());
+        assert!(crate::days::hashed(1, hashers::Kind::Fx).is_none());
+    }
+
+    #[test]
+    fn test_baseline_json() {
+        let mut baseline = Baseline::new();
+        baseline.insert(
+            7,
+            Record {
+                parse_ns: 1,
+                part1_ns: 2,
+                part2_ns: 3,
+            },
+        );
+        let json = serde_json::to_string(&baseline).unwrap();
+        assert_eq!(json, r#"{"7":{"parse_ns":1,"part1_ns":2,"part2_ns":3}}"#);
+        assert_eq!(serde_json::from_str::<Baseline>(&json).unwrap(), baseline);
+    }
+}
@@ -0,0 +1,51 @@
+use advent::Day;
+
+// Every day's registered solution, in order.
+pub fn all() -> Vec<&'static dyn Day> {
+    vec![
+        &day1::Puzzle,
+        &day2::Puzzle,
+        &day3::Puzzle,
+        &day4::Puzzle,
+        &day5::Puzzle,
+        &day6::Puzzle,
+        &day7::Puzzle,
+        &day8::Puzzle,
+        &day9::Puzzle,
+        &day10::Puzzle,
+        &day11::Puzzle,
+        &day12::Puzzle,
+        &day13::Puzzle,
+        &day14::Puzzle,
+        &day15::Puzzle,
+        &day16::Puzzle,
+        &day17::Puzzle,
+        &day18::Puzzle,
+        &day19::Puzzle,
+        &day20::Puzzle,
+        &day21::Puzzle,
+        &day22::Puzzle,
+        &day23::Puzzle,
+        &day24::Puzzle,
+        &day25::Puzzle,
+    ]
+}
+
+pub fn find(day: u32) -> Option<&'static dyn Day> {
+    all().into_iter().find(|d| d.day() == day)
+}
+
+// The days that keep big hash tables, built with the hasher that `kind` names, for
+// `advent bench --hashers`.
+pub fn hashed(day: u32, kind: hashers::Kind) -> Option<Box<dyn Day>> {
+    hashers::with_hasher!(kind, |S| {
+        let solution: Box<dyn Day> = match day {
+            15 => Box::new(day15::Hashed::<S>::default()),
+            17 => Box::new(day17::Hashed::<S>::default()),
+            22 => Box::new(day22::Hashed::<S>::default()),
+            24 => Box::new(day24::Hashed::<S>::default()),
+            _ => return None,
+        };
+        Some(solution)
+    })
+}
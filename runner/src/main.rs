@@ -0,0 +1,230 @@
+// advent: runs any day's solution from the top of the repository.
+//
+//   advent run <day|all> [part] [--input path|-] [--text input] [--answers path] [--json]
+//   advent bench [day|all] [--runs n] [--baseline path] [--save path] [--hashers]
+//   advent watch <day>
+//
+// Without --input or --text, each day reads $ADVENT_INPUTS/dayN.txt if that's set, or else the
+// input.txt in its own crate directory.  Answers go to stdout; everything else goes to stderr.
+//
+// Each answer is checked against the recorded answers for that input (see answers.rs) and marked
+// PASS, FAIL or UNKNOWN.  Any FAIL makes the exit status non-zero.  With --json, each answer is
+// printed as a line of JSON instead, for other programs (such as advent watch) to read.
+
+mod answers;
+mod bench;
+mod days;
+#[cfg(target_os = "linux")]
+mod watch;
+
+use advent::Source;
+use answers::{Answers, Verdict};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{Duration, Instant};
+
+const USAGE: &str =
+    "usage: advent run <day|all> [part] [--input path|-] [--text input] [--answers path] [--json]
+       advent bench [day|all] [--runs n] [--baseline path] [--save path] [--hashers]
+       advent watch <day>";
+
+struct RunArgs {
+    days: Vec<u32>,
+    parts: Vec<u32>,
+    input: Option<Source>,
+    answers: Option<PathBuf>,
+    json: bool,
+}
+
+// The result of solving one part of a day.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Outcome {
+    day: u32,
+    part: u32,
+    answer: String,
+    nanos: u64,
+    verdict: Verdict,
+}
+
+impl Outcome {
+    // One line describing the outcome.  Given the outcome of an earlier run of the same part,
+    // also say how the time and answer changed.
+    fn describe(&self, previous: Option<&Outcome>) -> String {
+        let time = format!("{:.3?}", Duration::from_nanos(self.nanos));
+        let (change, was) = match previous {
+            Some(p) if p.nanos > 0 => {
+                let change = self.nanos as f64 / p.nanos as f64 - 1.0;
+                let was = match p.answer == self.answer {
+                    true => String::new(),
+                    false => format!(" (was {})", p.answer),
+                };
+                (format!(", {:+.0}%", change * 100.0), was)
+            }
+            _ => (String::new(), String::new()),
+        };
+        format!(
+            "day {} part {}: {}{} ({}{}) {}",
+            self.day, self.part, self.answer, was, time, change, self.verdict
+        )
+    }
+}
+
+fn parse_number(text: &str, what: &str, max: u32) -> Result<u32, String> {
+    match text.parse::<u32>() {
+        Ok(n) if (1..=max).contains(&n) => Ok(n),
+        _ => Err(format!(
+            "{} must be between 1 and {}, got {:?}",
+            what, max, text
+        )),
+    }
+}
+
+fn parse_run_args(args: &[String]) -> Result<RunArgs, String> {
+    let mut positional: Vec<&str> = Vec::new();
+    let mut input: Option<Source> = None;
+    let mut answers: Option<PathBuf> = None;
+    let mut json = false;
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        let source = match arg.as_str() {
+            "--json" => {
+                json = true;
+                continue;
+            }
+            "--answers" | "-a" => match it.next() {
+                Some(path) => {
+                    answers = Some(PathBuf::from(path));
+                    continue;
+                }
+                None => return Err("--answers needs a path".to_string()),
+            },
+            "--input" | "-i" => match it.next() {
+                Some(path) => Source::from_arg(path),
+                None => return Err("--input needs a path".to_string()),
+            },
+            "--text" | "-t" => match it.next() {
+                Some(text) => Source::Inline(text.to_string()),
+                None => return Err("--text needs the input text".to_string()),
+            },
+            _ => {
+                positional.push(arg);
+                continue;
+            }
+        };
+        if input.is_some() {
+            return Err("only one of --input or --text can be given".to_string());
+        }
+        input = Some(source);
+    }
+    let days: Vec<u32> = match positional.first() {
+        None => return Err("missing day".to_string()),
+        Some(&"all") => (1..=25).collect(),
+        Some(day) => vec![parse_number(day, "day", 25)?],
+    };
+    let parts: Vec<u32> = match positional.get(1) {
+        None => vec![1, 2],
+        Some(part) => vec![parse_number(part, "part", 2)?],
+    };
+    if let Some(extra) = positional.get(2) {
+        return Err(format!("unexpected argument {:?}", extra));
+    }
+    if (input.is_some() || answers.is_some()) && days.len() > 1 {
+        return Err("--input, --text and --answers can only be used with a single day".to_string());
+    }
+    Ok(RunArgs {
+        days,
+        parts,
+        input,
+        answers,
+        json,
+    })
+}
+
+// The runner's crate directory sits next to each of the dayN crates.
+fn day_dir(day: u32) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(format!("day{}", day))
+}
+
+fn default_input_path(day: u32) -> PathBuf {
+    day_dir(day).join("input.txt")
+}
+
+// The answers recorded for the default input live beside it: $ADVENT_INPUTS/dayN.toml, or the
+// day's own answers.toml.  Nothing is known about other inputs unless --answers says so.
+fn load_answers(day: u32, args: &RunArgs) -> Result<Answers, String> {
+    let path = match (&args.answers, &args.input) {
+        (Some(path), _) => path.clone(),
+        (None, Some(_)) => return Ok(Answers::default()),
+        (None, None) => match env::var_os(advent::INPUTS_VAR) {
+            Some(dir) => Path::new(&dir).join(format!("day{}.toml", day)),
+            None => day_dir(day).join("answers.toml"),
+        },
+    };
+    Answers::load(day, &path)
+}
+
+fn read_input(day: u32, input: &Option<Source>) -> Result<String, String> {
+    let source = match input {
+        Some(source) => source.clone(),
+        None => Source::default_for(day, default_input_path(day)),
+    };
+    source.read().map_err(|e| e.to_string())
+}
+
+fn run(args: &RunArgs) -> Result<(), String> {
+    let mut failed = 0;
+    for &day in &args.days {
+        let solution = days::find(day).ok_or(format!("day {} is not registered", day))?;
+        let input = read_input(day, &args.input)?;
+        let answers = load_answers(day, args)?;
+        for &part in &args.parts {
+            let start = Instant::now();
+            let answer = match part {
+                1 => solution.part1(&input),
+                _ => solution.part2(&input),
+            }
+            .map_err(|e| format!("day {}: {}", day, e.report(&input)))?;
+            let outcome = Outcome {
+                day,
+                part,
+                nanos: start.elapsed().as_nanos() as u64,
+                verdict: answers.check(part, &answer),
+                answer,
+            };
+            if let Verdict::Fail(_) = outcome.verdict {
+                failed += 1;
+            }
+            if args.json {
+                println!("{}", serde_json::to_string(&outcome).unwrap());
+            } else {
+                println!("{}", outcome.describe(None));
+            }
+        }
+    }
+    match failed {
+        0 => Ok(()),
+        1 => Err("1 answer is wrong".to_string()),
+        n => Err(format!("{} answers are wrong", n)),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(|s| s.as_str()) {
+        Some("run") => parse_run_args(&args[1..]).and_then(|a| run(&a)),
+        Some("bench") => bench::parse_bench_args(&args[1..]).and_then(|a| bench::bench(&a)),
+        #[cfg(target_os = "linux")]
+        Some("watch") => watch::parse_watch_args(&args[1..]).and_then(watch::watch),
+        #[cfg(not(target_os = "linux"))]
+        Some("watch") => Err("watch uses inotify, so it only works on Linux".to_string()),
+        _ => Err(USAGE.to_string()),
+    };
+    if let Err(msg) = result {
+        eprintln!("advent: {}", msg);
+        process::exit(1);
+    }
+}
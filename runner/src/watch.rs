@@ -0,0 +1,258 @@
+// advent watch: re-runs a day whenever its code, input or answers change.
+//
+//   advent watch <day>
+//
+// Watches the day's crate directory and src/, the same for every crate it depends on by path
+// (such as handheld or automaton), and $ADVENT_INPUTS, if that's set, with inotify.
+// Every day is linked into the runner, so after a change the runner rebuilds itself with cargo
+// and runs the day in the fresh binary with --json.  Each answer is shown with its verdict and
+// how its time compares to the previous run.
+
+use crate::{day_dir, parse_number, Outcome};
+use inotify::{EventMask, Inotify, WatchDescriptor, WatchMask};
+use std::collections::HashSet;
+use std::env;
+use std::ffi::OsStr;
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+use toml::{Table, Value};
+
+// Editors often save a file in several steps; wait this long for them to finish.
+const SETTLE: Duration = Duration::from_millis(200);
+
+pub fn parse_watch_args(args: &[String]) -> Result<u32, String> {
+    match args {
+        [day] => parse_number(day, "day", 25),
+        [] => Err("missing day".to_string()),
+        [_, extra, ..] => Err(format!("unexpected argument {:?}", extra)),
+    }
+}
+
+// A directory being watched, and which of the files in it matter.
+struct Watched {
+    wd: WatchDescriptor,
+    // With a prefix, only files whose names start with it count; otherwise any source, input
+    // or answers file does.
+    prefix: Option<String>,
+}
+
+impl Watched {
+    fn wants(&self, name: &OsStr) -> bool {
+        let name = Path::new(name);
+        let relevant = matches!(
+            name.extension().and_then(|e| e.to_str()),
+            Some("rs") | Some("toml") | Some("txt")
+        );
+        let prefix = match &self.prefix {
+            Some(prefix) => name.to_string_lossy().starts_with(prefix.as_str()),
+            None => true,
+        };
+        relevant && prefix
+    }
+}
+
+// The `path` of each of a manifest's [dependencies] that has one, as written.  `name` is only
+// used in error messages.
+fn path_dependencies(name: &str, text: &str) -> Result<Vec<String>, String> {
+    let table: Table = text.parse().map_err(|e| format!("{}: {}", name, e))?;
+    let dependencies = match table.get("dependencies").and_then(Value::as_table) {
+        Some(dependencies) => dependencies,
+        None => return Ok(Vec::new()),
+    };
+    let paths = dependencies
+        .values()
+        .filter_map(|dependency| dependency.get("path").and_then(Value::as_str))
+        .map(str::to_string)
+        .collect();
+    Ok(paths)
+}
+
+// The day's crate and every crate it depends on by path, directly or not.
+fn crate_dirs(day: u32) -> Result<Vec<PathBuf>, String> {
+    let mut seen = HashSet::new();
+    let mut dirs = Vec::new();
+    let mut pending = vec![day_dir(day)];
+    while let Some(dir) = pending.pop() {
+        let dir = fs::canonicalize(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        if !seen.insert(dir.clone()) {
+            continue;
+        }
+        let manifest = dir.join("Cargo.toml");
+        let text =
+            fs::read_to_string(&manifest).map_err(|e| format!("{}: {}", manifest.display(), e))?;
+        for path in path_dependencies(&manifest.display().to_string(), &text)? {
+            pending.push(dir.join(path));
+        }
+        dirs.push(dir);
+    }
+    Ok(dirs)
+}
+
+// Each crate the day is built from, and every directory under its src/.  Watching directories
+// rather than files means editors that save by renaming a new file into place are still noticed.
+fn source_dirs(day: u32) -> Result<Vec<PathBuf>, String> {
+    let mut dirs = Vec::new();
+    let mut pending = Vec::new();
+    for dir in crate_dirs(day)? {
+        pending.push(dir.join("src"));
+        dirs.push(dir);
+    }
+    while let Some(dir) = pending.pop() {
+        let entries = fs::read_dir(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        for entry in entries.flatten() {
+            if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                pending.push(entry.path());
+            }
+        }
+        dirs.push(dir);
+    }
+    Ok(dirs)
+}
+
+fn add_watches(inotify: &mut Inotify, day: u32) -> Result<Vec<Watched>, String> {
+    let mask = WatchMask::CLOSE_WRITE | WatchMask::MOVED_TO | WatchMask::CREATE;
+    let add = |dir: &Path, prefix: Option<String>| {
+        let wd = inotify
+            .watches()
+            .add(dir, mask)
+            .map_err(|e| format!("{}: {}", dir.display(), e))?;
+        Ok::<Watched, String>(Watched { wd, prefix })
+    };
+    let mut watched = Vec::new();
+    for dir in source_dirs(day)? {
+        watched.push(add(&dir, None)?);
+    }
+    if let Some(dir) = env::var_os(advent::INPUTS_VAR) {
+        watched.push(add(Path::new(&dir), Some(format!("day{}.", day)))?);
+    }
+    Ok(watched)
+}
+
+// Block until something the day depends on changes.
+fn wait_for_change(inotify: &mut Inotify, watched: &[Watched]) -> Result<(), String> {
+    let mut buffer = [0; 4096];
+    loop {
+        let events = inotify
+            .read_events_blocking(&mut buffer)
+            .map_err(|e| format!("inotify: {}", e))?;
+        let changed = events.into_iter().any(|event| {
+            let name = match event.name {
+                Some(name) if !event.mask.contains(EventMask::ISDIR) => name,
+                _ => return false,
+            };
+            watched.iter().any(|w| w.wd == event.wd && w.wants(name))
+        });
+        if changed {
+            break;
+        }
+    }
+
+    // Swallow whatever else arrives while the editor finishes saving.
+    thread::sleep(SETTLE);
+    loop {
+        match inotify.read_events(&mut buffer) {
+            Ok(mut events) => {
+                if events.next().is_none() {
+                    return Ok(());
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+            Err(e) => return Err(format!("inotify: {}", e)),
+        }
+    }
+}
+
+// Rebuild the runner the same way this one was built.  Cargo's output is only shown if the
+// build fails, since it repeats every warning in every day on each build.
+fn rebuild() -> Result<bool, String> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+    let mut command = Command::new(cargo);
+    command.args(["build", "--quiet", "--manifest-path"]);
+    command.arg(&manifest);
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    let output = command
+        .output()
+        .map_err(|e| format!("running cargo: {}", e))?;
+    if !output.status.success() {
+        io::stderr().write_all(&output.stderr).unwrap_or(());
+    }
+    Ok(output.status.success())
+}
+
+// Run the day in the freshly built runner, `exe`.  Its errors go straight to stderr.
+fn run_day(exe: &Path, day: u32) -> Result<Vec<Outcome>, String> {
+    let output = Command::new(exe)
+        .args(["run", &day.to_string(), "--json"])
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| format!("{}: {}", exe.display(), e))?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| serde_json::from_str(line).map_err(|e| format!("{:?}: {}", line, e)))
+        .collect()
+}
+
+pub fn watch(day: u32) -> Result<(), String> {
+    // Once cargo replaces this binary, Linux reports its path as "... (deleted)", so look it up
+    // before the first build.
+    let exe = env::current_exe().map_err(|e| e.to_string())?;
+    let mut inotify = Inotify::init().map_err(|e| format!("inotify: {}", e))?;
+    let watched = add_watches(&mut inotify, day)?;
+    let mut previous: Vec<Outcome> = Vec::new();
+    loop {
+        eprintln!("advent: building day {}", day);
+        if rebuild()? {
+            let outcomes = run_day(&exe, day)?;
+            for outcome in &outcomes {
+                let before = previous.iter().find(|p| p.part == outcome.part);
+                println!("{}", outcome.describe(before));
+            }
+            if !outcomes.is_empty() {
+                previous = outcomes;
+            }
+        }
+        eprintln!("advent: watching day {} for changes", day);
+        wait_for_change(&mut inotify, &watched)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_watch_args() {
+        assert_eq!(parse_watch_args(&["7".to_string()]), Ok(7));
+        assert!(parse_watch_args(&[]).is_err());
+        assert!(parse_watch_args(&["26".to_string()]).is_err());
+        assert!(parse_watch_args(&["7".to_string(), "1".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_path_dependencies() {
+        let manifest = "[package]\nname = \"day8\"\n\n[dependencies]\n\
+            advent = { path = \"../advent\" }\nhandheld = { path = \"../handheld\" }\n\
+            regex = \"1\"\n";
+        let paths = path_dependencies("Cargo.toml", manifest).unwrap();
+        assert_eq!(paths, ["../advent", "../handheld"]);
+        assert_eq!(path_dependencies("Cargo.toml", "[package]\n"), Ok(vec![]));
+        assert!(path_dependencies("Cargo.toml", "[dependencies").is_err());
+    }
+
+    #[test]
+    fn test_source_dirs() {
+        let dirs = source_dirs(24).unwrap();
+        for name in ["day24", "automaton", "hexgrid", "hashers", "advent"] {
+            let src = fs::canonicalize(day_dir(24).join("..").join(name).join("src")).unwrap();
+            assert!(dirs.contains(&src), "{} isn't watched", src.display());
+        }
+    }
+}